@@ -0,0 +1,47 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares `make_shopping_list_c` with its Rayon equivalent, to show how
+//! many animals it takes before spreading the work across threads pays for
+//! itself. Run with `cargo bench -p pets --bench parallel`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pets::{make_shopping_list_c, par_make_shopping_list_c, Animal, PETS};
+
+const SIZES: [usize; 6] = [100, 1_000, 10_000, 100_000, 1_000_000, 4_000_000];
+
+fn menagerie(len: usize) -> Vec<Animal<'static>> {
+    PETS.iter().cycle().take(len).cloned().collect()
+}
+
+fn bench_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel");
+    group.sample_size(20);
+    for size in SIZES {
+        let pets = menagerie(size);
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("sequential", size), &pets, |b, pets| {
+            b.iter(|| make_shopping_list_c(black_box(pets)))
+        });
+        group.bench_with_input(BenchmarkId::new("rayon", size), &pets, |b, pets| {
+            b.iter(|| par_make_shopping_list_c(black_box(pets)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel);
+criterion_main!(benches);
@@ -0,0 +1,251 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Measures the indexed loop, the `for` loop and the iterator chain from
+//! [the bounds checks answer](https://cppfaq.rs/code.html#how-can-i-avoid-the-performance-penalty-of-bounds-checks)
+//! over ever larger menageries.
+//!
+//! Those build a `HashSet`, and hashing the meals costs far more than the
+//! loop does, so any difference from bounds checks is lost in the noise. The
+//! `count_hungry` variants do the same three kinds of loop with nothing but a
+//! counter in the body, which is where bounds checks (and vectorization)
+//! would show up.
+//!
+//! Run with `cargo bench -p pets --bench shopping_list`. As well as the usual
+//! Criterion output, this writes `pets/benches/results/shopping_list.json`
+//! and `shopping_list.md` next to it, which are checked in so the book can
+//! embed the numbers. Both record the compiler and machine they came from:
+//! numbers from one run on one machine are a data point, not a verdict. Set `SHOPPING_LIST_REPORT_DIR` to write them somewhere
+//! else. Only benchmarks measured by this run are reported, so a filtered run
+//! gives a partial report rather than mixing in old results.
+
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::SystemTime;
+
+use criterion::{criterion_group, BenchmarkId, Criterion, Throughput};
+use pets::{make_shopping_list_a, make_shopping_list_b, make_shopping_list_c, Animal, PETS};
+use serde_json::{json, Value};
+
+const GROUP: &str = "shopping_list";
+
+const SIZES: [usize; 4] = [1_000, 100_000, 1_000_000, 4_000_000];
+
+/// Each variant returns a number, so that its work can't be optimized away.
+type Variant = fn(&[Animal<'static>]) -> usize;
+
+const VARIANTS: [(&str, Variant); 6] = [
+    ("make_shopping_list_a", |pets| {
+        make_shopping_list_a(pets).len()
+    }),
+    ("make_shopping_list_b", |pets| {
+        make_shopping_list_b(pets).len()
+    }),
+    ("make_shopping_list_c", |pets| {
+        make_shopping_list_c(pets).len()
+    }),
+    ("count_hungry_a", count_hungry_a),
+    ("count_hungry_b", count_hungry_b),
+    ("count_hungry_c", count_hungry_c),
+];
+
+/// `make_shopping_list_a`'s loop, without the `HashSet`.
+fn count_hungry_a(pets: &[Animal]) -> usize {
+    let mut hungry = 0;
+    #[allow(clippy::needless_range_loop)] // that's the point of this one
+    for n in 0..pets.len() {
+        if pets[n].is_hungry {
+            hungry += 1;
+        }
+    }
+    hungry
+}
+
+/// `make_shopping_list_b`'s loop, without the `HashSet`.
+fn count_hungry_b(pets: &[Animal]) -> usize {
+    let mut hungry = 0;
+    for animal in pets {
+        if animal.is_hungry {
+            hungry += 1;
+        }
+    }
+    hungry
+}
+
+/// `make_shopping_list_c`'s iterator chain, without the `HashSet`.
+fn count_hungry_c(pets: &[Animal]) -> usize {
+    pets.iter().filter(|animal| animal.is_hungry).count()
+}
+
+/// `PETS`, repeated until there are `len` of them.
+fn menagerie(len: usize) -> Vec<Animal<'static>> {
+    PETS.iter().cycle().take(len).cloned().collect()
+}
+
+fn bench_shopping_lists(c: &mut Criterion) {
+    let mut group = c.benchmark_group(GROUP);
+    group.sample_size(20);
+    for size in SIZES {
+        let pets = menagerie(size);
+        group.throughput(Throughput::Elements(size as u64));
+        for (name, variant) in VARIANTS {
+            group.bench_with_input(BenchmarkId::new(name, size), &pets, |b, pets| {
+                b.iter(|| variant(black_box(pets)))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_shopping_lists);
+
+/// Where Criterion puts its results. This follows the same rules as Criterion
+/// itself, except that we fall back to the workspace `target` directory rather
+/// than asking `cargo metadata`.
+fn criterion_home() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        PathBuf::from(home)
+    } else if let Some(target) = std::env::var_os("CARGO_TARGET_DIR") {
+        PathBuf::from(target).join("criterion")
+    } else {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/criterion")
+    }
+}
+
+/// Where to write the report.
+fn report_dir() -> PathBuf {
+    match std::env::var_os("SHOPPING_LIST_REPORT_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("benches/results"),
+    }
+}
+
+/// Reads back the median time Criterion measured for one benchmark, if it
+/// was measured since `started`. Older estimates are left over from previous
+/// runs, for instance when this one was filtered or run with `--test`.
+fn median_ns(home: &Path, name: &str, size: usize, started: SystemTime) -> Option<f64> {
+    let path = home
+        .join(GROUP)
+        .join(name)
+        .join(size.to_string())
+        .join("new/estimates.json");
+    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+    if modified < started {
+        return None;
+    }
+    let estimates: Value = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+    estimates["median"]["point_estimate"].as_f64()
+}
+
+/// The compiler and machine the numbers came from.
+fn environment() -> Value {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let rustc = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_owned());
+    // Linux only; elsewhere the architecture will have to do.
+    let cpu = fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+        info.lines()
+            .find_map(|line| line.strip_prefix("model name")?.split_once(':'))
+            .map(|(_, model)| model.trim().to_owned())
+    });
+    json!({
+        "rustc": rustc,
+        "profile": "bench",
+        "arch": env::consts::ARCH,
+        "os": env::consts::OS,
+        "cpu": cpu,
+        "cpus": thread::available_parallelism().map(|n| n.get()).ok(),
+    })
+}
+
+fn write_report(home: &Path, started: SystemTime, dir: &Path) -> io::Result<()> {
+    let environment = environment();
+    let mut results = Vec::new();
+    let mut table = format!(
+        "Median ns per animal, from one run of `cargo bench -p pets --bench {GROUP}`.\n\
+         The `make_shopping_list` columns are dominated by hashing; the\n\
+         `count_hungry` ones time the loops alone.\n\n\
+         * Compiler: {}\n\
+         * CPU: {} ({}, {} available)\n\n\
+         | Animals |",
+        environment["rustc"].as_str().unwrap_or("an unknown rustc"),
+        environment["cpu"].as_str().unwrap_or("an unknown CPU"),
+        environment["arch"].as_str().unwrap_or_default(),
+        environment["cpus"],
+    );
+    for (name, _) in VARIANTS {
+        table.push_str(&format!(" `{name}` |"));
+    }
+    table.push_str("\n|--------:|");
+    table.push_str(&"----:|".repeat(VARIANTS.len()));
+    table.push('\n');
+    for size in SIZES {
+        let medians: Vec<_> = VARIANTS
+            .iter()
+            .map(|(name, _)| median_ns(home, name, size, started))
+            .collect();
+        if medians.iter().all(Option::is_none) {
+            continue;
+        }
+        table.push_str(&format!("| {size} |"));
+        for ((name, _), median) in VARIANTS.iter().zip(&medians) {
+            match median {
+                Some(median) => {
+                    let per_animal = median / size as f64;
+                    results.push(json!({
+                        "function": name,
+                        "animals": size,
+                        "median_ns": median,
+                        "ns_per_animal": per_animal,
+                    }));
+                    table.push_str(&format!(" {per_animal:.2} |"));
+                }
+                None => table.push_str(" - |"),
+            }
+        }
+        table.push('\n');
+    }
+    if results.is_empty() {
+        // Nothing was measured, e.g. because of `--test` or a filter.
+        return Ok(());
+    }
+    fs::create_dir_all(dir)?;
+    fs::write(
+        dir.join(format!("{GROUP}.json")),
+        serde_json::to_string_pretty(&json!({
+            "environment": environment,
+            "results": results,
+        }))? + "\n",
+    )?;
+    fs::write(dir.join(format!("{GROUP}.md")), table)
+}
+
+fn main() {
+    let started = SystemTime::now();
+    benches();
+    Criterion::default().configure_from_args().final_summary();
+    let dir = report_dir();
+    if let Err(e) = write_report(&criterion_home(), started, &dir) {
+        eprintln!("Unable to write report to {}: {e}", dir.display());
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Something which lives with us and needs feeding.
+#[derive(Clone, Debug)]
+pub struct Animal<'a> {
+    pub kind: &'a str,
+    pub is_hungry: bool,
+    pub meal_needed: &'a str,
+}
+
+/// The animals we need to shop for.
+pub static PETS: [Animal<'static>; 4] = [
+    Animal {
+        kind: "Dog",
+        is_hungry: true,
+        meal_needed: "Kibble",
+    },
+    Animal {
+        kind: "Python",
+        is_hungry: false,
+        meal_needed: "Cat",
+    },
+    Animal {
+        kind: "Cat",
+        is_hungry: true,
+        meal_needed: "Kibble",
+    },
+    Animal {
+        kind: "Lion",
+        is_hungry: false,
+        meal_needed: "Kibble",
+    },
+];
+
+/// A duck which isn't ours, but which we feed anyway.
+pub static NEARBY_DUCK: Animal<'static> = Animal {
+    kind: "Duck",
+    is_hungry: true,
+    meal_needed: "pondweed",
+};
+
+/// Somewhere animals live which aren't our pets, but which we feed anyway.
+#[derive(Clone, Debug, Default)]
+pub struct Pond<'a> {
+    inhabitants: std::borrow::Cow<'a, [Animal<'a>]>,
+}
+
+/// The pond at the bottom of our garden.
+pub static MY_POND: Pond<'static> = Pond::with_inhabitants(&[
+    Animal {
+        kind: "Goldfish",
+        is_hungry: true,
+        meal_needed: "Fish flakes",
+    },
+    Animal {
+        kind: "Frog",
+        is_hungry: false,
+        meal_needed: "Flies",
+    },
+]);
+
+impl<'a> Pond<'a> {
+    /// An empty pond.
+    pub const fn new() -> Self {
+        Self::with_inhabitants(&[])
+    }
+
+    /// A pond which starts out with `inhabitants` living in it. This is
+    /// `const`, so it can be used for statics like [`MY_POND`].
+    pub const fn with_inhabitants(inhabitants: &'a [Animal<'a>]) -> Self {
+        Self {
+            inhabitants: std::borrow::Cow::Borrowed(inhabitants),
+        }
+    }
+
+    /// Something moves in.
+    pub fn add(&mut self, animal: Animal<'a>) {
+        self.inhabitants.to_mut().push(animal);
+    }
+
+    /// The first animal of the given kind moves out, if there is one.
+    pub fn remove_kind(&mut self, kind: &str) -> Option<Animal<'a>> {
+        let index = self
+            .inhabitants
+            .iter()
+            .position(|animal| animal.kind == kind)?;
+        Some(self.inhabitants.to_mut().remove(index))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inhabitants.is_empty()
+    }
+
+    /// How many animals live here.
+    pub fn len(&self) -> usize {
+        self.inhabitants.len()
+    }
+
+    /// How many animals of the given kind live here.
+    pub fn count_kind(&self, kind: &str) -> usize {
+        self.inhabitants
+            .iter()
+            .filter(|animal| animal.kind == kind)
+            .count()
+    }
+
+    pub fn contains_kind(&self, kind: &str) -> bool {
+        self.count_kind(kind) > 0
+    }
+
+    /// Everything living in the pond.
+    pub fn inhabitants(&self) -> std::slice::Iter<'_, Animal<'a>> {
+        self.inhabitants.iter()
+    }
+}
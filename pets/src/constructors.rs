@@ -0,0 +1,127 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The constructors from
+//! [How do I overload constructors?](https://cppfaq.rs/signatures.html#how-do-i-overload-constructors),
+//! for real.
+//!
+//! [`Animal`] has no sensible default, so it gets a `new_<species>()` for
+//! each species we know how to feed. [`Racoon`] does have a default, so it
+//! gets a `new()` and then `with_age()` and `with_hunger()` for the common
+//! variations. For anything else, both have public fields, so struct update
+//! syntax fills the gap: `Racoon { is_hungry: false, ..Racoon::with_age(3) }`.
+//!
+//! These live here rather than in `animal.rs`, which the book includes at
+//! the top of every shopping list example.
+
+use crate::Animal;
+
+impl Animal<'static> {
+    /// Constructors start hungry: that's when you need to know what to buy.
+    const fn hungry(kind: &'static str, meal_needed: &'static str) -> Self {
+        Animal {
+            kind,
+            is_hungry: true,
+            meal_needed,
+        }
+    }
+
+    pub const fn new_dog() -> Self {
+        Self::hungry("Dog", "Kibble")
+    }
+
+    pub const fn new_python() -> Self {
+        Self::hungry("Python", "Cat")
+    }
+
+    pub const fn new_cat() -> Self {
+        Self::hungry("Cat", "Kibble")
+    }
+
+    pub const fn new_lion() -> Self {
+        Self::hungry("Lion", "Kibble")
+    }
+
+    pub const fn new_duck() -> Self {
+        Self::hungry("Duck", "pondweed")
+    }
+
+    pub const fn new_squirrel() -> Self {
+        Self::hungry("Squirrel", "Nuts")
+    }
+
+    pub const fn new_badger() -> Self {
+        Self::hungry("Badger", "Earthworms")
+    }
+
+    pub const fn new_racoon() -> Self {
+        Racoon::new().as_animal()
+    }
+}
+
+/// A racoon, which unlike most of our animals has an age that matters:
+/// kits under a year old still need milk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Racoon {
+    pub age: usize,
+    pub is_hungry: bool,
+}
+
+impl Racoon {
+    /// A hungry adult.
+    pub const fn new() -> Self {
+        Racoon {
+            age: 2,
+            is_hungry: true,
+        }
+    }
+
+    pub const fn with_age(age: usize) -> Self {
+        Racoon { age, ..Self::new() }
+    }
+
+    pub const fn with_hunger(is_hungry: bool) -> Self {
+        Racoon {
+            is_hungry,
+            ..Self::new()
+        }
+    }
+
+    /// This racoon as one of the animals we shop for.
+    pub const fn as_animal(&self) -> Animal<'static> {
+        Animal {
+            kind: "Racoon",
+            is_hungry: self.is_hungry,
+            meal_needed: if self.age == 0 { "Milk" } else { "Leftovers" },
+        }
+    }
+}
+
+impl Default for Racoon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Racoon> for Animal<'static> {
+    fn from(racoon: &Racoon) -> Self {
+        racoon.as_animal()
+    }
+}
+
+impl From<Racoon> for Animal<'static> {
+    fn from(racoon: Racoon) -> Self {
+        racoon.as_animal()
+    }
+}
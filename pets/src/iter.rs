@@ -0,0 +1,63 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shopping lists for any iterator of animals, not just `PETS`.
+//!
+//! As [the book says](https://cppfaq.rs/signatures.html#should-i-return-an-iterator-or-a-collection),
+//! pretty much always return an iterator. [`hungry_meals`] does, and only
+//! [`shopping_list`] decides to collect.
+
+use std::collections::HashSet;
+
+use crate::Animal;
+
+/// Adds `.hungry()` and `.meals()` to any iterator of animals:
+///
+/// ```
+/// use pets::{AnimalIteratorExt, MY_POND, PETS};
+///
+/// let meals: Vec<_> = PETS.iter().chain(MY_POND.inhabitants()).hungry().meals().collect();
+/// assert_eq!(meals, ["Kibble", "Kibble", "Fish flakes"]);
+/// ```
+pub trait AnimalIteratorExt<'r, 'a: 'r>: Iterator<Item = &'r Animal<'a>> + Sized {
+    /// Only the animals which need feeding.
+    fn hungry(self) -> impl Iterator<Item = &'r Animal<'a>> {
+        self.filter(|animal| animal.is_hungry)
+    }
+
+    /// What each animal eats, whether or not it's hungry.
+    fn meals(self) -> impl Iterator<Item = &'a str> {
+        self.map(|animal| animal.meal_needed)
+    }
+}
+
+impl<'r, 'a: 'r, I: Iterator<Item = &'r Animal<'a>>> AnimalIteratorExt<'r, 'a> for I {}
+
+/// The meals needed by the hungry animals in `animals`, one per animal,
+/// without collecting them anywhere.
+pub fn hungry_meals<'r, 'a: 'r, I>(animals: I) -> impl Iterator<Item = &'a str> + use<'r, 'a, I>
+where
+    I: IntoIterator<Item = &'r Animal<'a>>,
+{
+    animals.into_iter().hungry().meals()
+}
+
+/// [`make_shopping_list_c`](crate::make_shopping_list_c) for any source of
+/// animals: a slice, a `Vec`, a chain of several, a pond...
+pub fn shopping_list<'r, 'a: 'r, I>(animals: I) -> HashSet<&'a str>
+where
+    I: IntoIterator<Item = &'r Animal<'a>>,
+{
+    hungry_meals(animals).collect()
+}
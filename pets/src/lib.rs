@@ -0,0 +1,49 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The pets which appear in the examples in
+//! [Questions about code in function bodies](https://cppfaq.rs/code.html),
+//! and the shopping lists the book makes for them. Real inventories can be
+//! loaded from TOML, JSON or CSV using [`roster`]. The book uses strings for
+//! species and meals; [`typed`] has the enum-based equivalents. [`safety`]
+//! checks that nobody's dinner is another pet, and [`quantities`] works out
+//! how much of each meal to buy. [`simulation`] lets them get hungry again.
+//! [`Animal`] has a `new_<species>()` for each species, and [`Racoon`]
+//! shows the `new()` plus `with_*()` style of constructor.
+//!
+//! The book includes `animal.rs` as hidden lines at the top of its examples,
+//! so keep that file free of anything which would stop it compiling as a
+//! standalone snippet (for instance, `use crate::...`).
+
+mod animal;
+mod constructors;
+mod iter;
+mod parallel;
+mod pond;
+pub mod quantities;
+pub mod roster;
+pub mod safety;
+mod shopping;
+pub mod simulation;
+pub mod typed;
+
+pub use animal::{Animal, Pond, MY_POND, NEARBY_DUCK, PETS};
+pub use constructors::Racoon;
+pub use iter::{hungry_meals, shopping_list, AnimalIteratorExt};
+pub use parallel::{par_make_shopping_list_c, par_make_shopping_list_d};
+pub use pond::{pond_inhabitant, pond_inhabitants};
+pub use shopping::{
+    make_shopping_list_a, make_shopping_list_b, make_shopping_list_c, make_shopping_list_d,
+    make_shopping_list_e,
+};
@@ -0,0 +1,39 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use rayon::prelude::*;
+
+use crate::{Animal, NEARBY_DUCK};
+
+/// [`make_shopping_list_c`](crate::make_shopping_list_c), spread across
+/// threads with Rayon. Each thread builds its own set and the sets are then
+/// unioned, so the result is exactly the sequential one.
+pub fn par_make_shopping_list_c<'a>(pets: &[Animal<'a>]) -> HashSet<&'a str> {
+    pets.par_iter()
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
+
+/// [`make_shopping_list_d`](crate::make_shopping_list_d), spread across
+/// threads with Rayon.
+pub fn par_make_shopping_list_d<'a>(pets: &[Animal<'a>]) -> HashSet<&'a str> {
+    pets.par_iter()
+        .chain(rayon::iter::once(&NEARBY_DUCK))
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
@@ -0,0 +1,39 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Animal, Pond};
+
+/// Returns the first animal living in the pond, if there is one. `Option` is
+/// iterable, so this can be chained onto another iterator; see
+/// [`make_shopping_list_e`](crate::make_shopping_list_e).
+pub fn pond_inhabitant<'p, 'a>(pond: &'p Pond<'a>) -> Option<&'p Animal<'a>> {
+    pond.inhabitants().next()
+}
+
+/// Returns everything living in the pond, ready to be chained onto
+/// `PETS.iter()`:
+///
+/// ```
+/// use pets::{pond_inhabitants, MY_POND, PETS};
+///
+/// let hungry = PETS
+///     .iter()
+///     .chain(pond_inhabitants(&MY_POND))
+///     .filter(|animal| animal.is_hungry)
+///     .count();
+/// assert_eq!(hungry, 3);
+/// ```
+pub fn pond_inhabitants<'p, 'a>(pond: &'p Pond<'a>) -> std::slice::Iter<'p, Animal<'a>> {
+    pond.inhabitants()
+}
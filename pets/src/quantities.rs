@@ -0,0 +1,223 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shopping lists which know how much to buy.
+//!
+//! `make_shopping_list_c` collects into a `HashSet`, so a hungry dog and a
+//! hungry cat who both want kibble come out as a single "Kibble". A
+//! [`ShoppingList`] adds up each animal's portion instead, while still being
+//! something you can `collect()` an iterator into.
+//!
+//! Portions are `u32`, but totals are `u64`: a few million lions at 7 kg each
+//! is already more grams than fit in a `u32`.
+
+use std::collections::btree_map::{self, BTreeMap};
+use std::fmt;
+
+use crate::typed::{Animal, Meal, Species};
+
+/// What a [`Meal`] is measured in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    Grams,
+    /// Whole items, for meals which don't come in bags.
+    Each,
+}
+
+impl Meal {
+    /// The unit this meal is bought in.
+    pub fn unit(self) -> Unit {
+        match self {
+            Meal::Kibble
+            | Meal::Pondweed
+            | Meal::FishFlakes
+            | Meal::Flies
+            | Meal::Nuts
+            | Meal::Earthworms
+            | Meal::Milk
+            | Meal::Leftovers => Unit::Grams,
+            Meal::Cat => Unit::Each,
+        }
+    }
+}
+
+/// An amount of some meal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quantity {
+    pub amount: u64,
+    pub unit: Unit,
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.unit {
+            Unit::Grams => write!(f, "{} g", self.amount),
+            Unit::Each => write!(f, "{}", self.amount),
+        }
+    }
+}
+
+/// How much one animal of each species eats at a sitting, in the unit of
+/// whichever meal it needs.
+#[derive(Clone, Debug, Default)]
+pub struct PortionTable {
+    overrides: BTreeMap<Species, u32>,
+}
+
+impl PortionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Changes the portion for one species.
+    pub fn with_portion(mut self, species: Species, amount: u32) -> Self {
+        self.overrides.insert(species, amount);
+        self
+    }
+
+    pub fn portion(&self, species: Species) -> u32 {
+        self.overrides
+            .get(&species)
+            .copied()
+            .unwrap_or(match species {
+                Species::Dog => 400,
+                Species::Python => 1,
+                Species::Cat => 60,
+                Species::Lion => 7000,
+                Species::Duck => 150,
+                Species::Goldfish => 1,
+                Species::Frog => 5,
+                Species::Squirrel => 30,
+                Species::Badger => 200,
+                Species::Racoon => 250,
+            })
+    }
+}
+
+/// How much of each meal to buy, kept in a stable order.
+///
+/// Collecting `(Meal, amount)` pairs adds up the amounts for each meal:
+///
+/// ```
+/// use pets::quantities::ShoppingList;
+/// use pets::typed::Meal;
+///
+/// let list: ShoppingList = [(Meal::Kibble, 400), (Meal::Kibble, 60)].into_iter().collect();
+/// assert_eq!(list.get(Meal::Kibble).unwrap().to_string(), "460 g");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShoppingList {
+    amounts: BTreeMap<Meal, u64>,
+}
+
+impl ShoppingList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything the hungry animals in `pets` need, in the portions given by
+    /// `portions`.
+    pub fn for_pets(pets: &[Animal], portions: &PortionTable) -> Self {
+        pets.iter()
+            .filter(|animal| animal.is_hungry)
+            .map(|animal| {
+                (
+                    animal.meal_needed,
+                    u64::from(portions.portion(animal.species)),
+                )
+            })
+            .collect()
+    }
+
+    /// Adds `amount` of `meal`, in [`Meal::unit`]s.
+    pub fn add(&mut self, meal: Meal, amount: u64) {
+        *self.amounts.entry(meal).or_default() += amount;
+    }
+
+    /// Removes `amount` of `meal`, if there's that much on the list. Returns
+    /// whether it did.
+    pub fn take(&mut self, meal: Meal, amount: u64) -> bool {
+        match self.amounts.entry(meal) {
+            btree_map::Entry::Occupied(mut entry) if *entry.get() >= amount => {
+                *entry.get_mut() -= amount;
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Adds everything on `other` to this list.
+    pub fn merge(&mut self, other: ShoppingList) {
+        self.extend(other);
+    }
+
+    pub fn get(&self, meal: Meal) -> Option<Quantity> {
+        self.amounts.get(&meal).map(|&amount| Quantity {
+            amount,
+            unit: meal.unit(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.amounts.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.amounts.len()
+    }
+
+    /// Each meal and how much of it to buy, in [`Meal`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (Meal, Quantity)> + '_ {
+        self.amounts
+            .keys()
+            .map(|&meal| (meal, self.get(meal).unwrap()))
+    }
+}
+
+impl FromIterator<(Meal, u64)> for ShoppingList {
+    fn from_iter<I: IntoIterator<Item = (Meal, u64)>>(iter: I) -> Self {
+        let mut list = ShoppingList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl Extend<(Meal, u64)> for ShoppingList {
+    fn extend<I: IntoIterator<Item = (Meal, u64)>>(&mut self, iter: I) {
+        for (meal, amount) in iter {
+            self.add(meal, amount);
+        }
+    }
+}
+
+impl IntoIterator for ShoppingList {
+    type Item = (Meal, u64);
+    type IntoIter = btree_map::IntoIter<Meal, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.amounts.into_iter()
+    }
+}
+
+impl fmt::Display for ShoppingList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (meal, quantity) in self.iter() {
+            writeln!(f, "{meal}: {quantity}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Animal;
+
+/// An [`Animal`] which owns its strings, so that it can be loaded from a file
+/// rather than compiled in.
+///
+/// To make a shopping list, borrow each one as an [`Animal`]:
+///
+/// ```
+/// let roster = pets::roster::from_json_str(
+///     r#"[{ "kind": "Hamster", "is_hungry": true, "meal_needed": "Seeds" }]"#,
+/// )
+/// .unwrap();
+/// let animals: Vec<_> = roster.iter().map(pets::roster::OwnedAnimal::as_animal).collect();
+/// assert!(pets::make_shopping_list_c(&animals).contains("Seeds"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedAnimal {
+    pub kind: String,
+    pub is_hungry: bool,
+    pub meal_needed: String,
+}
+
+impl OwnedAnimal {
+    pub fn as_animal(&self) -> Animal<'_> {
+        Animal {
+            kind: &self.kind,
+            is_hungry: self.is_hungry,
+            meal_needed: &self.meal_needed,
+        }
+    }
+}
+
+impl From<&Animal<'_>> for OwnedAnimal {
+    fn from(animal: &Animal<'_>) -> Self {
+        Self {
+            kind: animal.kind.to_owned(),
+            is_hungry: animal.is_hungry,
+            meal_needed: animal.meal_needed.to_owned(),
+        }
+    }
+}
+
+/// The file formats a roster can be written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// An array of tables called `animal`, i.e. `[[animal]]` sections.
+    Toml,
+    /// An array of objects.
+    Json,
+    /// A header row of `kind,is_hungry,meal_needed`, then one animal per row.
+    Csv,
+}
+
+impl Format {
+    /// Guesses the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "toml" => Some(Format::Toml),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Format::Toml => "TOML",
+            Format::Json => "JSON",
+            Format::Csv => "CSV",
+        })
+    }
+}
+
+/// Why a roster couldn't be loaded.
+#[derive(Debug)]
+pub enum RosterError {
+    Io(io::Error),
+    /// The file's extension didn't tell us its format.
+    UnknownFormat,
+    /// The file was read, but its contents were wrong. `line` is 1-based.
+    Parse {
+        format: Format,
+        line: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for RosterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RosterError::Io(e) => write!(f, "unable to read roster: {e}"),
+            RosterError::UnknownFormat => f.write_str("roster must be .toml, .json or .csv"),
+            RosterError::Parse {
+                format,
+                line: Some(line),
+                message,
+            } => write!(f, "bad {format} roster at line {line}: {message}"),
+            RosterError::Parse {
+                format,
+                line: None,
+                message,
+            } => write!(f, "bad {format} roster: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RosterError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RosterError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RosterError {
+    fn from(e: io::Error) -> Self {
+        RosterError::Io(e)
+    }
+}
+
+/// Loads a roster, working out its format from the file extension.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<OwnedAnimal>, RosterError> {
+    let path = path.as_ref();
+    let format = Format::from_path(path).ok_or(RosterError::UnknownFormat)?;
+    from_str(&fs::read_to_string(path)?, format)
+}
+
+/// Parses a roster which has already been read into memory.
+pub fn from_str(input: &str, format: Format) -> Result<Vec<OwnedAnimal>, RosterError> {
+    match format {
+        Format::Toml => from_toml_str(input),
+        Format::Json => from_json_str(input),
+        Format::Csv => from_csv_str(input),
+    }
+}
+
+pub fn from_toml_str(input: &str) -> Result<Vec<OwnedAnimal>, RosterError> {
+    #[derive(Deserialize)]
+    struct Roster {
+        #[serde(default)]
+        animal: Vec<OwnedAnimal>,
+    }
+    toml::from_str::<Roster>(input)
+        .map(|roster| roster.animal)
+        .map_err(|e| RosterError::Parse {
+            format: Format::Toml,
+            // TOML errors only know their byte offset.
+            line: e
+                .span()
+                .map(|span| input[..span.start].matches('\n').count() + 1),
+            message: e.message().to_owned(),
+        })
+}
+
+pub fn from_json_str(input: &str) -> Result<Vec<OwnedAnimal>, RosterError> {
+    serde_json::from_str(input).map_err(|e| RosterError::Parse {
+        format: Format::Json,
+        line: Some(e.line()).filter(|line| *line != 0),
+        message: e.to_string(),
+    })
+}
+
+pub fn from_csv_str(input: &str) -> Result<Vec<OwnedAnimal>, RosterError> {
+    csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes())
+        .deserialize()
+        .map(|record| {
+            record.map_err(|e| RosterError::Parse {
+                format: Format::Csv,
+                line: e.position().map(|position| position.line() as usize),
+                message: match e.kind() {
+                    csv::ErrorKind::Deserialize { err, .. } => err.to_string(),
+                    _ => e.to_string(),
+                },
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,104 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Spots when one pet's dinner is another pet.
+//!
+//! `PETS` contains both a Python whose `meal_needed` is "Cat", and a Cat. The
+//! shopping list functions are happy to buy whatever is asked for; use
+//! [`conflicts`] to notice first, or [`checked_shopping_list`] to refuse.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{make_shopping_list_c, Animal};
+
+/// One pet which would eat another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conflict<'a> {
+    /// Index of the hungry party in the slice which was checked.
+    pub predator: usize,
+    pub predator_kind: &'a str,
+    /// Index of the menu item in the slice which was checked.
+    pub prey: usize,
+    pub prey_kind: &'a str,
+    /// Whether the predator is hungry, so its meal would be on the shopping
+    /// list right now.
+    pub on_shopping_list: bool,
+}
+
+impl fmt::Display for Conflict<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (#{}) eats {} (#{})",
+            self.predator_kind, self.predator, self.prey_kind, self.prey
+        )?;
+        if self.on_shopping_list {
+            f.write_str(", and is hungry")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every pair of pets where one's `meal_needed` is the other's `kind`,
+/// ignoring case.
+///
+/// The pets are indexed by kind up front, so this takes time in proportion
+/// to the number of pets plus the number of conflicts, rather than comparing
+/// every pet with every other.
+pub fn conflicts<'r, 'a>(pets: &'r [Animal<'a>]) -> impl Iterator<Item = Conflict<'a>> + 'r {
+    let mut by_kind: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, animal) in pets.iter().enumerate() {
+        by_kind
+            .entry(animal.kind.to_ascii_lowercase())
+            .or_default()
+            .push(index);
+    }
+    // Found up front, because an iterator can't borrow `by_kind` from its
+    // own closure.
+    let mut conflicts = Vec::new();
+    for (predator, hunter) in pets.iter().enumerate() {
+        let Some(prey) = by_kind.get(&hunter.meal_needed.to_ascii_lowercase()) else {
+            continue;
+        };
+        conflicts.extend(
+            prey.iter()
+                .filter(|&&prey| prey != predator)
+                .map(|&prey| Conflict {
+                    predator,
+                    predator_kind: hunter.kind,
+                    prey,
+                    prey_kind: pets[prey].kind,
+                    on_shopping_list: hunter.is_hungry,
+                }),
+        );
+    }
+    conflicts.into_iter()
+}
+
+/// Like [`make_shopping_list_c`], but refuses if the list would include one
+/// of the pets. Conflicts with predators which aren't hungry yet are not an
+/// error.
+pub fn checked_shopping_list<'a>(
+    pets: &[Animal<'a>],
+) -> Result<HashSet<&'a str>, Vec<Conflict<'a>>> {
+    let dangerous: Vec<_> = conflicts(pets)
+        .filter(|conflict| conflict.on_shopping_list)
+        .collect();
+    if dangerous.is_empty() {
+        Ok(make_shopping_list_c(pets))
+    } else {
+        Err(dangerous)
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use crate::{pond_inhabitant, Animal, Pond, NEARBY_DUCK};
+
+/// Works out what to buy using an index into `pets`. Ugh.
+pub fn make_shopping_list_a<'a>(pets: &[Animal<'a>]) -> HashSet<&'a str> {
+    let mut meals_needed = HashSet::new();
+    #[allow(clippy::needless_range_loop)] // that's the point of this one
+    for n in 0..pets.len() {
+        if pets[n].is_hungry {
+            meals_needed.insert(pets[n].meal_needed);
+        }
+    }
+    meals_needed
+}
+
+/// Works out what to buy by looping over an iterator. Better...
+pub fn make_shopping_list_b<'a>(pets: &[Animal<'a>]) -> HashSet<&'a str> {
+    let mut meals_needed = HashSet::new();
+    for animal in pets.iter() {
+        if animal.is_hungry {
+            meals_needed.insert(animal.meal_needed);
+        }
+    }
+    meals_needed
+}
+
+/// Works out what to buy with a chain of iterators. Best...
+pub fn make_shopping_list_c<'a>(pets: &[Animal<'a>]) -> HashSet<&'a str> {
+    pets.iter()
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
+
+/// Like [`make_shopping_list_c`], but also feeds [`NEARBY_DUCK`].
+pub fn make_shopping_list_d<'a>(pets: &[Animal<'a>]) -> HashSet<&'a str> {
+    pets.iter()
+        .chain(std::iter::once(&NEARBY_DUCK))
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
+
+/// Like [`make_shopping_list_c`], but also feeds the first animal living in
+/// `pond`.
+pub fn make_shopping_list_e<'a>(pets: &[Animal<'a>], pond: &Pond<'a>) -> HashSet<&'a str> {
+    pets.iter()
+        .chain(pond_inhabitant(pond))
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
@@ -0,0 +1,191 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pets which get hungry, and get fed, as time passes.
+//!
+//! Everything random comes from a seeded [`ChaCha8Rng`], so a [`Simulation`]
+//! with a given seed always plays out the same way.
+
+use rand::rngs::ChaCha8Rng;
+use rand::{RngExt, SeedableRng};
+
+use crate::quantities::{PortionTable, ShoppingList};
+use crate::typed::{Animal, Species};
+
+/// How quickly a species gets through its food.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Metabolism {
+    /// How many ticks a meal usually lasts.
+    pub ticks_per_meal: u32,
+    /// How many ticks either side of `ticks_per_meal` it might actually last.
+    pub variation: u32,
+}
+
+impl Species {
+    pub fn metabolism(self) -> Metabolism {
+        let (ticks_per_meal, variation) = match self {
+            Species::Dog => (8, 2),
+            Species::Python => (240, 48),
+            Species::Cat => (6, 2),
+            Species::Lion => (24, 6),
+            Species::Duck => (4, 1),
+            Species::Goldfish => (12, 4),
+            Species::Frog => (16, 4),
+            Species::Squirrel => (4, 1),
+            Species::Badger => (12, 3),
+            Species::Racoon => (8, 2),
+        };
+        Metabolism {
+            ticks_per_meal,
+            variation,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Resident {
+    animal: Animal,
+    /// Ticks until this animal is hungry again.
+    fullness: u32,
+}
+
+/// A household of animals, a pantry, and a clock.
+#[derive(Debug)]
+pub struct Simulation {
+    residents: Vec<Resident>,
+    pantry: ShoppingList,
+    portions: PortionTable,
+    feeding_interval: Option<u32>,
+    rng: ChaCha8Rng,
+    now: u64,
+}
+
+impl Simulation {
+    /// Starts a simulation of `animals`. Animals which aren't hungry yet are
+    /// part way through their last meal.
+    pub fn new(animals: impl IntoIterator<Item = Animal>, seed: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let residents = animals
+            .into_iter()
+            .map(|animal| {
+                let fullness = if animal.is_hungry {
+                    0
+                } else {
+                    rng.random_range(1..=animal.species.metabolism().ticks_per_meal)
+                };
+                Resident { animal, fullness }
+            })
+            .collect();
+        Self {
+            residents,
+            pantry: ShoppingList::new(),
+            portions: PortionTable::new(),
+            feeding_interval: None,
+            rng,
+            now: 0,
+        }
+    }
+
+    pub fn with_portions(mut self, portions: PortionTable) -> Self {
+        self.portions = portions;
+        self
+    }
+
+    /// Feeds everyone who's hungry every `ticks` ticks. Without this, nobody
+    /// is fed unless you call [`Simulation::feed`].
+    pub fn with_feeding_every(mut self, ticks: u32) -> Self {
+        self.feeding_interval = Some(ticks.max(1));
+        self
+    }
+
+    /// Puts the shopping in the pantry.
+    pub fn stock(&mut self, shopping: ShoppingList) {
+        self.pantry.merge(shopping);
+    }
+
+    /// Feeds every hungry animal whose meal is in the pantry. Returns how
+    /// many were fed.
+    pub fn feed(&mut self) -> usize {
+        let mut fed = 0;
+        for resident in &mut self.residents {
+            let animal = &mut resident.animal;
+            if animal.is_hungry
+                && self.pantry.take(
+                    animal.meal_needed,
+                    u64::from(self.portions.portion(animal.species)),
+                )
+            {
+                let metabolism = animal.species.metabolism();
+                let low = metabolism
+                    .ticks_per_meal
+                    .saturating_sub(metabolism.variation)
+                    .max(1);
+                let high = metabolism.ticks_per_meal + metabolism.variation;
+                resident.fullness = self.rng.random_range(low..=high);
+                animal.is_hungry = false;
+                fed += 1;
+            }
+        }
+        fed
+    }
+
+    /// Advances the clock by one tick.
+    pub fn tick(&mut self) {
+        self.now += 1;
+        for resident in &mut self.residents {
+            resident.fullness = resident.fullness.saturating_sub(1);
+            if resident.fullness == 0 {
+                resident.animal.is_hungry = true;
+            }
+        }
+        if let Some(interval) = self.feeding_interval {
+            if self.now % u64::from(interval) == 0 {
+                self.feed();
+            }
+        }
+    }
+
+    pub fn run(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// How many ticks have passed.
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// The animals as they are now.
+    pub fn animals(&self) -> impl Iterator<Item = &Animal> + '_ {
+        self.residents.iter().map(|resident| &resident.animal)
+    }
+
+    pub fn pantry(&self) -> &ShoppingList {
+        &self.pantry
+    }
+
+    /// What to buy to feed everyone who's hungry right now.
+    pub fn shopping_list(&self) -> ShoppingList {
+        self.animals()
+            .filter(|animal| animal.is_hungry)
+            .map(|animal| {
+                (
+                    animal.meal_needed,
+                    u64::from(self.portions.portion(animal.species)),
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The same animals as the crate root, but with [enums instead of
+//! strings](https://cppfaq.rs/types.html), so that a typo in a meal is a
+//! compile error rather than an empty bowl.
+//!
+//! The string-based [`crate::Animal`] is still what the book uses; convert
+//! between the two with `From` and `TryFrom`.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Generates an enum whose variants each have a canonical name, and which
+/// parses those names case-insensitively.
+macro_rules! named_enum {
+    ($(#[$meta:meta])* $name:ident, $what:literal { $($variant:ident => $text:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum $name {
+            $($variant,)*
+        }
+
+        impl $name {
+            /// Every variant, in declaration order.
+            pub const ALL: &'static [$name] = &[$($name::$variant,)*];
+
+            /// The name used for this variant by the string-based API.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $($name::$variant => $text,)*
+                }
+            }
+
+            /// The variant whose name is exactly `name`. Unlike `parse`,
+            /// this works in `const` contexts.
+            pub const fn from_name(name: &str) -> Option<Self> {
+                $(
+                    if str_eq(name, $text) {
+                        return Some($name::$variant);
+                    }
+                )*
+                None
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseNameError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|variant| variant.as_str().eq_ignore_ascii_case(s))
+                    .ok_or_else(|| ParseNameError {
+                        what: $what,
+                        name: s.to_owned(),
+                    })
+            }
+        }
+    };
+}
+
+/// `a == b`, for `const fn`s.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+named_enum! {
+    /// What sort of animal something is.
+    Species, "species" {
+        Dog => "Dog",
+        Python => "Python",
+        Cat => "Cat",
+        Lion => "Lion",
+        Duck => "Duck",
+        Goldfish => "Goldfish",
+        Frog => "Frog",
+        Squirrel => "Squirrel",
+        Badger => "Badger",
+        Racoon => "Racoon",
+    }
+}
+
+named_enum! {
+    /// Something we can buy at the petshop.
+    Meal, "meal" {
+        Kibble => "Kibble",
+        Cat => "Cat",
+        Pondweed => "pondweed",
+        FishFlakes => "Fish flakes",
+        Flies => "Flies",
+        Nuts => "Nuts",
+        Earthworms => "Earthworms",
+        Milk => "Milk",
+        Leftovers => "Leftovers",
+    }
+}
+
+/// A name which isn't one of the variants of [`Species`] or [`Meal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseNameError {
+    what: &'static str,
+    name: String,
+}
+
+impl fmt::Display for ParseNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown {} {:?}", self.what, self.name)
+    }
+}
+
+impl std::error::Error for ParseNameError {}
+
+/// Like [`crate::Animal`], but typed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Animal {
+    pub species: Species,
+    pub is_hungry: bool,
+    pub meal_needed: Meal,
+}
+
+/// Converts one of the crate's own animals at compile time. Fails to
+/// compile if it has a species or meal we don't know.
+const fn typed(animal: &crate::Animal<'_>) -> Animal {
+    let Some(species) = Species::from_name(animal.kind) else {
+        panic!("unknown species");
+    };
+    let Some(meal_needed) = Meal::from_name(animal.meal_needed) else {
+        panic!("unknown meal");
+    };
+    Animal {
+        species,
+        is_hungry: animal.is_hungry,
+        meal_needed,
+    }
+}
+
+/// [`crate::PETS`], typed. Derived from it at compile time, so the two
+/// can't drift apart.
+pub static PETS: [Animal; crate::PETS.len()] = {
+    const PLACEHOLDER: Animal = typed(&crate::NEARBY_DUCK);
+    let mut pets = [PLACEHOLDER; crate::PETS.len()];
+    let mut i = 0;
+    while i < pets.len() {
+        pets[i] = typed(&crate::PETS[i]);
+        i += 1;
+    }
+    pets
+};
+
+/// [`crate::NEARBY_DUCK`], typed.
+pub static NEARBY_DUCK: Animal = typed(&crate::NEARBY_DUCK);
+
+impl TryFrom<&crate::Animal<'_>> for Animal {
+    type Error = ParseNameError;
+
+    fn try_from(animal: &crate::Animal<'_>) -> Result<Self, Self::Error> {
+        Ok(Animal {
+            species: animal.kind.parse()?,
+            is_hungry: animal.is_hungry,
+            meal_needed: animal.meal_needed.parse()?,
+        })
+    }
+}
+
+impl From<&Animal> for crate::Animal<'static> {
+    fn from(animal: &Animal) -> Self {
+        crate::Animal {
+            kind: animal.species.as_str(),
+            is_hungry: animal.is_hungry,
+            meal_needed: animal.meal_needed.as_str(),
+        }
+    }
+}
+
+/// Like [`crate::Pond`], but typed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Pond {
+    inhabitants: Vec<Animal>,
+}
+
+impl Pond {
+    /// Everything living in the pond.
+    pub fn inhabitants(&self) -> std::slice::Iter<'_, Animal> {
+        self.inhabitants.iter()
+    }
+}
+
+impl TryFrom<&crate::Pond<'_>> for Pond {
+    type Error = ParseNameError;
+
+    fn try_from(pond: &crate::Pond<'_>) -> Result<Self, Self::Error> {
+        Ok(Pond {
+            inhabitants: pond
+                .inhabitants()
+                .map(Animal::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// The typed version of [`crate::pond_inhabitant`].
+pub fn pond_inhabitant(pond: &Pond) -> Option<&Animal> {
+    pond.inhabitants.first()
+}
+
+/// The typed version of [`crate::make_shopping_list_a`].
+pub fn make_shopping_list_a(pets: &[Animal]) -> HashSet<Meal> {
+    let mut meals_needed = HashSet::new();
+    #[allow(clippy::needless_range_loop)] // that's the point of this one
+    for n in 0..pets.len() {
+        if pets[n].is_hungry {
+            meals_needed.insert(pets[n].meal_needed);
+        }
+    }
+    meals_needed
+}
+
+/// The typed version of [`crate::make_shopping_list_b`].
+pub fn make_shopping_list_b(pets: &[Animal]) -> HashSet<Meal> {
+    let mut meals_needed = HashSet::new();
+    for animal in pets.iter() {
+        if animal.is_hungry {
+            meals_needed.insert(animal.meal_needed);
+        }
+    }
+    meals_needed
+}
+
+/// The typed version of [`crate::make_shopping_list_c`].
+pub fn make_shopping_list_c(pets: &[Animal]) -> HashSet<Meal> {
+    pets.iter()
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
+
+/// The typed version of [`crate::make_shopping_list_d`].
+pub fn make_shopping_list_d(pets: &[Animal]) -> HashSet<Meal> {
+    pets.iter()
+        .chain(std::iter::once(&NEARBY_DUCK))
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
+
+/// The typed version of [`crate::make_shopping_list_e`].
+pub fn make_shopping_list_e(pets: &[Animal], pond: &Pond) -> HashSet<Meal> {
+    pets.iter()
+        .chain(pond_inhabitant(pond))
+        .filter(|animal| animal.is_hungry)
+        .map(|animal| animal.meal_needed)
+        .collect()
+}
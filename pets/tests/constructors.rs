@@ -0,0 +1,101 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pets::typed::{self, Meal, Species};
+use pets::{
+    make_shopping_list_a, make_shopping_list_c, make_shopping_list_d, shopping_list, Animal,
+    Racoon, PETS,
+};
+
+#[test]
+fn species_constructors_match_the_pets() {
+    let constructed = [
+        Animal::new_dog(),
+        Animal::new_python(),
+        Animal::new_cat(),
+        Animal::new_lion(),
+    ];
+    for (constructed, pet) in constructed.iter().zip(&PETS) {
+        assert_eq!(constructed.kind, pet.kind);
+        assert_eq!(constructed.meal_needed, pet.meal_needed);
+        assert!(constructed.is_hungry);
+    }
+}
+
+#[test]
+fn racoon_constructors() {
+    assert_eq!(Racoon::new(), Racoon::default());
+    assert_eq!(Racoon::with_age(5).age, 5);
+    assert!(Racoon::with_age(5).is_hungry);
+    assert!(!Racoon::with_hunger(false).is_hungry);
+    let kit = Racoon {
+        is_hungry: false,
+        ..Racoon::with_age(0)
+    };
+    assert_eq!(kit.as_animal().meal_needed, "Milk");
+    assert!(!Animal::from(kit).is_hungry);
+    assert_eq!(Animal::new_racoon().meal_needed, "Leftovers");
+}
+
+#[test]
+fn shopping_lists_accept_constructed_animals() {
+    let animals = [
+        Animal::new_squirrel(),
+        Animal::new_badger(),
+        Animal::from(Racoon::with_age(0)),
+        Animal::from(&Racoon::with_hunger(false)),
+        Animal {
+            is_hungry: false,
+            ..Animal::new_dog()
+        },
+        Animal::new_duck(),
+    ];
+    let expected = ["Earthworms", "Milk", "Nuts", "pondweed"];
+    let mut list: Vec<_> = make_shopping_list_c(&animals).into_iter().collect();
+    list.sort();
+    assert_eq!(list, expected);
+    assert_eq!(
+        make_shopping_list_a(&animals),
+        make_shopping_list_c(&animals)
+    );
+    // The nearby duck is already on the list.
+    let mut list: Vec<_> = make_shopping_list_d(&animals).into_iter().collect();
+    list.sort();
+    assert_eq!(list, expected);
+    assert_eq!(shopping_list(&animals), make_shopping_list_c(&animals));
+}
+
+#[test]
+fn constructed_animals_have_types() {
+    let animals = [
+        Animal::new_squirrel(),
+        Animal::new_badger(),
+        Animal::new_racoon(),
+        Animal::from(Racoon::with_age(0)),
+    ];
+    let typed: Vec<_> = animals
+        .iter()
+        .map(|animal| typed::Animal::try_from(animal).unwrap())
+        .map(|animal| (animal.species, animal.meal_needed))
+        .collect();
+    assert_eq!(
+        typed,
+        [
+            (Species::Squirrel, Meal::Nuts),
+            (Species::Badger, Meal::Earthworms),
+            (Species::Racoon, Meal::Leftovers),
+            (Species::Racoon, Meal::Milk),
+        ]
+    );
+}
@@ -0,0 +1,80 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The book claims that the indexed loop, the `for` loop and the iterator
+//! chain all make the same shopping list. Check that for arbitrary pets, and
+//! that the Rayon versions agree with the sequential ones.
+
+use pets::{
+    make_shopping_list_a, make_shopping_list_b, make_shopping_list_c, make_shopping_list_d,
+    make_shopping_list_e, par_make_shopping_list_c, par_make_shopping_list_d, shopping_list,
+    Animal, Pond, MY_POND, NEARBY_DUCK, PETS,
+};
+use proptest::prelude::*;
+use proptest::sample::select;
+
+const KINDS: &[&str] = &["Dog", "Python", "Cat", "Lion", "Duck", "Goldfish"];
+const MEALS: &[&str] = &["Kibble", "Cat", "pondweed", "Flakes", "Mice"];
+
+fn animal() -> impl Strategy<Value = Animal<'static>> {
+    (select(KINDS), any::<bool>(), select(MEALS)).prop_map(|(kind, is_hungry, meal_needed)| {
+        Animal {
+            kind,
+            is_hungry,
+            meal_needed,
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn a_b_and_c_agree(pets in prop::collection::vec(animal(), 0..64)) {
+        let a = make_shopping_list_a(&pets);
+        prop_assert_eq!(&a, &make_shopping_list_b(&pets));
+        prop_assert_eq!(&a, &make_shopping_list_c(&pets));
+        prop_assert_eq!(&a, &shopping_list(&pets));
+    }
+
+    #[test]
+    fn d_adds_the_duck(pets in prop::collection::vec(animal(), 0..64)) {
+        let mut expected = make_shopping_list_c(&pets);
+        expected.insert(NEARBY_DUCK.meal_needed);
+        prop_assert_eq!(make_shopping_list_d(&pets), expected);
+    }
+
+    #[test]
+    fn parallel_matches_sequential(pets in prop::collection::vec(animal(), 0..4096)) {
+        prop_assert_eq!(par_make_shopping_list_c(&pets), make_shopping_list_c(&pets));
+        prop_assert_eq!(par_make_shopping_list_d(&pets), make_shopping_list_d(&pets));
+    }
+
+    #[test]
+    fn e_with_an_empty_pond_is_c(pets in prop::collection::vec(animal(), 0..64)) {
+        prop_assert_eq!(make_shopping_list_e(&pets, &Pond::new()), make_shopping_list_c(&pets));
+    }
+
+    #[test]
+    fn e_feeds_the_goldfish(pets in prop::collection::vec(animal(), 0..64)) {
+        let mut expected = make_shopping_list_c(&pets);
+        expected.insert("Fish flakes");
+        prop_assert_eq!(make_shopping_list_e(&pets, &MY_POND), expected);
+    }
+}
+
+#[test]
+fn book_pets() {
+    let list = make_shopping_list_c(&PETS);
+    assert_eq!(list.len(), 1);
+    assert!(list.contains("Kibble"));
+}
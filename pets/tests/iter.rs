@@ -0,0 +1,54 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::Cell;
+use std::collections::HashSet;
+
+use pets::{hungry_meals, shopping_list, Animal, MY_POND, NEARBY_DUCK, PETS};
+
+#[test]
+fn hungry_meals_skips_the_full() {
+    let meals: Vec<_> = hungry_meals(&PETS).collect();
+    assert_eq!(meals, ["Kibble", "Kibble"]);
+    assert_eq!(hungry_meals(&[] as &[Animal]).count(), 0);
+}
+
+#[test]
+fn hungry_meals_takes_any_source() {
+    let meals: Vec<_> = hungry_meals(
+        PETS.iter()
+            .chain(MY_POND.inhabitants())
+            .chain([&NEARBY_DUCK]),
+    )
+    .collect();
+    assert_eq!(meals, ["Kibble", "Kibble", "Fish flakes", "pondweed"]);
+    assert_eq!(
+        shopping_list(PETS.iter().chain([&NEARBY_DUCK])),
+        HashSet::from(["Kibble", "pondweed"])
+    );
+}
+
+#[test]
+fn hungry_meals_is_lazy() {
+    let pulled = Cell::new(0);
+    let counted = PETS.iter().inspect(|_| pulled.set(pulled.get() + 1));
+    let mut meals = hungry_meals(counted);
+    assert_eq!(pulled.get(), 0);
+    // The dog is first, and hungry, so nothing else needs looking at yet.
+    assert_eq!(meals.next(), Some("Kibble"));
+    assert_eq!(pulled.get(), 1);
+    // The python isn't hungry, so the next meal is the cat's.
+    assert_eq!(meals.next(), Some("Kibble"));
+    assert_eq!(pulled.get(), 3);
+}
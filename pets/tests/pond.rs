@@ -0,0 +1,50 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pets::{pond_inhabitant, pond_inhabitants, Animal, Pond, MY_POND, NEARBY_DUCK};
+
+#[test]
+fn my_pond_is_occupied() {
+    assert_eq!(MY_POND.len(), 2);
+    assert!(MY_POND.contains_kind("Frog"));
+    assert_eq!(pond_inhabitant(&MY_POND).unwrap().kind, "Goldfish");
+}
+
+#[test]
+fn animals_move_in_and_out() {
+    let mut pond = Pond::new();
+    assert!(pond.is_empty());
+    assert!(pond_inhabitant(&pond).is_none());
+
+    pond.add(NEARBY_DUCK.clone());
+    pond.add(Animal {
+        kind: "Duck",
+        is_hungry: false,
+        meal_needed: "pondweed",
+    });
+    assert_eq!(pond.count_kind("Duck"), 2);
+
+    let duck = pond.remove_kind("Duck").unwrap();
+    assert!(duck.is_hungry);
+    assert_eq!(pond.len(), 1);
+    assert!(pond.remove_kind("Heron").is_none());
+}
+
+#[test]
+fn adding_to_a_static_pond_copies_it() {
+    let mut pond = MY_POND.clone();
+    pond.add(NEARBY_DUCK.clone());
+    assert_eq!(pond_inhabitants(&pond).count(), 3);
+    assert_eq!(MY_POND.len(), 2);
+}
@@ -0,0 +1,60 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pets::quantities::{PortionTable, Quantity, ShoppingList, Unit};
+use pets::typed::{Animal, Meal, Species, NEARBY_DUCK, PETS};
+
+#[test]
+fn hungry_pets_share_the_kibble() {
+    let list = ShoppingList::for_pets(&PETS, &PortionTable::new());
+    assert_eq!(list.len(), 1);
+    assert_eq!(
+        list.get(Meal::Kibble),
+        Some(Quantity {
+            amount: 460,
+            unit: Unit::Grams
+        })
+    );
+    assert_eq!(list.get(Meal::Cat), None);
+}
+
+#[test]
+fn portions_can_be_overridden() {
+    let portions = PortionTable::new().with_portion(Species::Dog, 500);
+    let list = ShoppingList::for_pets(&PETS, &portions);
+    assert_eq!(list.get(Meal::Kibble).unwrap().amount, 560);
+}
+
+#[test]
+fn merging_adds_amounts() {
+    let mut list = ShoppingList::for_pets(&PETS, &PortionTable::new());
+    list.merge(ShoppingList::for_pets(
+        std::slice::from_ref(&NEARBY_DUCK),
+        &PortionTable::new(),
+    ));
+    list.merge([(Meal::Kibble, 40), (Meal::Cat, 1)].into_iter().collect());
+    assert_eq!(list.to_string(), "Kibble: 500 g\nCat: 1\npondweed: 150 g\n");
+}
+
+#[test]
+fn a_huge_pride_does_not_overflow() {
+    let lion = Animal {
+        species: Species::Lion,
+        is_hungry: true,
+        meal_needed: Meal::Kibble,
+    };
+    let pride = vec![lion; 5_000_000];
+    let list = ShoppingList::for_pets(&pride, &PortionTable::new());
+    assert_eq!(list.get(Meal::Kibble).unwrap().amount, 35_000_000_000);
+}
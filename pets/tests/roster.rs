@@ -0,0 +1,101 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use pets::make_shopping_list_c;
+use pets::roster::{self, Format, OwnedAnimal, RosterError};
+
+const TOML: &str = r#"
+[[animal]]
+kind = "Dog"
+is_hungry = true
+meal_needed = "Kibble"
+
+[[animal]]
+kind = "Tortoise"
+is_hungry = true
+meal_needed = "Lettuce"
+"#;
+
+const JSON: &str = r#"[
+    { "kind": "Dog", "is_hungry": true, "meal_needed": "Kibble" },
+    { "kind": "Tortoise", "is_hungry": true, "meal_needed": "Lettuce" }
+]"#;
+
+const CSV: &str = "\
+kind,is_hungry,meal_needed
+Dog,true,Kibble
+Tortoise,true,Lettuce
+";
+
+fn shopping_list(roster: &[OwnedAnimal]) -> HashSet<String> {
+    let animals: Vec<_> = roster.iter().map(OwnedAnimal::as_animal).collect();
+    make_shopping_list_c(&animals)
+        .into_iter()
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+fn all_formats_agree() {
+    let toml = roster::from_str(TOML, Format::Toml).unwrap();
+    assert_eq!(toml.len(), 2);
+    assert_eq!(toml, roster::from_str(JSON, Format::Json).unwrap());
+    assert_eq!(toml, roster::from_str(CSV, Format::Csv).unwrap());
+    assert_eq!(
+        shopping_list(&toml),
+        HashSet::from(["Kibble".to_owned(), "Lettuce".to_owned()])
+    );
+}
+
+fn error_line(result: Result<Vec<OwnedAnimal>, RosterError>) -> Option<usize> {
+    match result {
+        Err(RosterError::Parse { line, .. }) => line,
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+}
+
+#[test]
+fn bad_toml_reports_line() {
+    let bad = TOML.replace(
+        "is_hungry = true\nmeal_needed = \"Lettuce\"",
+        "is_hungry = \"very\"\nmeal_needed = \"Lettuce\"",
+    );
+    assert_eq!(error_line(roster::from_toml_str(&bad)), Some(9));
+}
+
+#[test]
+fn bad_json_reports_line() {
+    let bad = JSON.replace(
+        "\"is_hungry\": true, \"meal_needed\": \"Lettuce\"",
+        "\"is_hungry\": true",
+    );
+    assert_eq!(error_line(roster::from_json_str(&bad)), Some(3));
+}
+
+#[test]
+fn bad_csv_reports_line() {
+    let bad = CSV.replace("Tortoise,true", "Tortoise,maybe");
+    let error = roster::from_csv_str(&bad).unwrap_err();
+    assert!(error.to_string().contains("line 3"), "{error}");
+}
+
+#[test]
+fn load_uses_extension() {
+    assert!(matches!(
+        roster::load("menagerie.yaml"),
+        Err(RosterError::UnknownFormat)
+    ));
+}
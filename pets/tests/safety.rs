@@ -0,0 +1,76 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pets::safety::{checked_shopping_list, conflicts, Conflict};
+use pets::{Animal, PETS};
+
+#[test]
+fn python_would_eat_cat() {
+    let found: Vec<_> = conflicts(&PETS).collect();
+    assert_eq!(
+        found,
+        [Conflict {
+            predator: 1,
+            predator_kind: "Python",
+            prey: 2,
+            prey_kind: "Cat",
+            on_shopping_list: false,
+        }]
+    );
+    assert_eq!(found[0].to_string(), "Python (#1) eats Cat (#2)");
+    // The python isn't hungry, so shopping is still allowed.
+    assert!(checked_shopping_list(&PETS).is_ok());
+}
+
+#[test]
+fn hungry_python_is_rejected() {
+    let mut pets = PETS.to_vec();
+    pets[1].is_hungry = true;
+    let rejected = checked_shopping_list(&pets).unwrap_err();
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(
+        rejected[0].to_string(),
+        "Python (#1) eats Cat (#2), and is hungry"
+    );
+}
+
+#[test]
+fn kinds_match_ignoring_case() {
+    let pets = [
+        Animal {
+            kind: "Heron",
+            is_hungry: true,
+            meal_needed: "goldfish",
+        },
+        Animal {
+            kind: "Goldfish",
+            is_hungry: true,
+            meal_needed: "Flakes",
+        },
+    ];
+    assert_eq!(conflicts(&pets).count(), 1);
+}
+
+#[test]
+fn cannibals_skip_themselves() {
+    let shark = Animal {
+        kind: "Shark",
+        is_hungry: true,
+        meal_needed: "shark",
+    };
+    let found: Vec<_> = conflicts(&[shark.clone(), shark.clone(), shark])
+        .map(|c| (c.predator, c.prey))
+        .collect();
+    assert_eq!(found, [(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]);
+}
@@ -0,0 +1,63 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pets::quantities::{PortionTable, ShoppingList};
+use pets::simulation::Simulation;
+use pets::typed::{Meal, PETS};
+
+fn hunger_trace(seed: u64, ticks: u32) -> Vec<Vec<bool>> {
+    let mut simulation = Simulation::new(PETS.iter().cloned(), seed).with_feeding_every(4);
+    simulation.stock([(Meal::Kibble, 20_000)].into_iter().collect());
+    (0..ticks)
+        .map(|_| {
+            simulation.tick();
+            simulation
+                .animals()
+                .map(|animal| animal.is_hungry)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn same_seed_same_story() {
+    assert_eq!(hunger_trace(42, 200), hunger_trace(42, 200));
+    // Otherwise a simulation which ignored its seed would pass too.
+    assert_ne!(hunger_trace(42, 200), hunger_trace(43, 200));
+}
+
+#[test]
+fn everyone_gets_hungry_eventually() {
+    let mut simulation = Simulation::new(PETS.iter().cloned(), 7);
+    simulation.run(1000);
+    assert_eq!(simulation.now(), 1000);
+    assert!(simulation.animals().all(|animal| animal.is_hungry));
+}
+
+#[test]
+fn feeding_uses_up_the_pantry() {
+    let portions = PortionTable::new();
+    let mut simulation = Simulation::new(PETS.iter().cloned(), 1).with_portions(portions.clone());
+    let needed = simulation.shopping_list();
+    assert_eq!(needed, ShoppingList::for_pets(&PETS, &portions));
+
+    simulation.stock(needed);
+    assert_eq!(simulation.feed(), 2);
+    assert!(simulation.pantry().is_empty());
+    assert!(simulation.shopping_list().is_empty());
+
+    // Nothing left for the second sitting.
+    simulation.run(500);
+    assert_eq!(simulation.feed(), 0);
+}
@@ -0,0 +1,107 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pets::typed::{self, Meal, Species};
+
+#[test]
+fn parsing_ignores_case() {
+    assert_eq!("kibble".parse(), Ok(Meal::Kibble));
+    assert_eq!("PONDWEED".parse(), Ok(Meal::Pondweed));
+    assert_eq!(" python ".parse(), Ok(Species::Python));
+    let error = "Tortoise".parse::<Species>().unwrap_err();
+    assert_eq!(error.to_string(), "unknown species \"Tortoise\"");
+}
+
+#[test]
+fn display_round_trips() {
+    for meal in Meal::ALL {
+        assert_eq!(meal.to_string().parse(), Ok(*meal));
+    }
+    for species in Species::ALL {
+        assert_eq!(species.to_string().parse(), Ok(*species));
+    }
+}
+
+#[test]
+fn typed_pets_match_string_pets() {
+    for (typed, untyped) in typed::PETS.iter().zip(pets::PETS.iter()) {
+        assert_eq!(typed::Animal::try_from(untyped).as_ref(), Ok(typed));
+        let back = pets::Animal::from(typed);
+        assert_eq!(back.kind, untyped.kind);
+        assert_eq!(back.meal_needed, untyped.meal_needed);
+    }
+}
+
+#[test]
+fn typed_shopping_lists_match_string_ones() {
+    let names = |meals: std::collections::HashSet<Meal>| {
+        meals
+            .into_iter()
+            .map(Meal::as_str)
+            .collect::<std::collections::HashSet<_>>()
+    };
+    assert_eq!(
+        names(typed::make_shopping_list_a(&typed::PETS)),
+        pets::make_shopping_list_a(&pets::PETS)
+    );
+    assert_eq!(
+        names(typed::make_shopping_list_b(&typed::PETS)),
+        pets::make_shopping_list_b(&pets::PETS)
+    );
+    assert_eq!(
+        names(typed::make_shopping_list_c(&typed::PETS)),
+        pets::make_shopping_list_c(&pets::PETS)
+    );
+    assert_eq!(
+        names(typed::make_shopping_list_d(&typed::PETS)),
+        pets::make_shopping_list_d(&pets::PETS)
+    );
+
+    let pond = typed::Pond::try_from(&pets::MY_POND).unwrap();
+    let typed_list = typed::make_shopping_list_e(&typed::PETS, &pond);
+    assert!(typed_list.contains(&Meal::FishFlakes));
+    assert_eq!(
+        names(typed_list),
+        pets::make_shopping_list_e(&pets::PETS, &pets::MY_POND)
+    );
+}
+
+#[test]
+fn typed_pets_are_derived_from_string_pets() {
+    assert_eq!(typed::PETS.len(), pets::PETS.len());
+    assert_eq!(
+        typed::Animal::try_from(&pets::NEARBY_DUCK).as_ref(),
+        Ok(&typed::NEARBY_DUCK)
+    );
+    assert_eq!(Meal::from_name("pondweed"), Some(Meal::Pondweed));
+    assert_eq!(Meal::from_name("Pondweed"), None);
+}
+
+#[test]
+fn typed_pond_rejects_unknown_inhabitants() {
+    let mut pond = pets::MY_POND.clone();
+    let typed_pond = typed::Pond::try_from(&pond).unwrap();
+    assert_eq!(
+        typed::pond_inhabitant(&typed_pond).map(|animal| animal.species),
+        Some(Species::Goldfish)
+    );
+
+    pond.add(pets::Animal {
+        kind: "Newt",
+        is_hungry: true,
+        meal_needed: "Flies",
+    });
+    let error = typed::Pond::try_from(&pond).unwrap_err();
+    assert_eq!(error.to_string(), r#"unknown species "Newt""#);
+}
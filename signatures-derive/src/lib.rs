@@ -0,0 +1,319 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `#[derive(Builder)]`, which writes the kind of builder that
+//! [How do I overload constructors?](https://cppfaq.rs/signatures.html#how-do-i-overload-constructors)
+//! writes by hand.
+//!
+//! ```
+//! use signatures_derive::Builder;
+//!
+//! #[derive(Builder)]
+//! struct BirthdayCard {
+//!     name: String,
+//!     age: Option<i32>,
+//!     text: String,
+//! }
+//!
+//! let card = BirthdayCardBuilder::new()
+//!     .name("Paul")
+//!     .text("Happy Valentine's Day!")
+//!     .age(64)
+//!     .build();
+//! ```
+//!
+//! Every setter takes `&mut self`, as the book recommends, and
+//! `impl Into<T>`. Fields of type `Option<T>` are optional, and their setters
+//! return `&mut Self`. Every other field is required, and forgetting one is a
+//! compile error rather than a runtime one: the builder has a type parameter
+//! per required field, recording whether it's been set, and `build` only
+//! exists once they all have been. So the setter for a required field
+//! returns a new builder, in its new state, with a copy of everything set so
+//! far. The states are marker types in a module named after the builder, so
+//! a missing `text` shows up as
+//!
+//! ```text
+//! no method named `build` found for mutable reference `&mut BirthdayCardBuilder<NameSet, TextUnset>`
+//! ```
+//!
+//! `build` takes `&self`, so one builder can build several values, and
+//! therefore needs every field to be `Clone`, as do the setters for required
+//! fields.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Ident, PathArguments, Type,
+};
+
+#[proc_macro_derive(Builder)]
+pub fn derive_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A field of the struct being built.
+struct Field<'a> {
+    name: &'a Ident,
+    ty: &'a Type,
+    /// For optional fields, the `T` in `Option<T>`.
+    optional: Option<&'a Type>,
+}
+
+/// A required field's type parameter and the marker types it can be.
+struct State {
+    param: Ident,
+    set: Ident,
+    unset: Ident,
+}
+
+/// If `ty` is `Option<T>`, returns `T`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+/// `birthday_card` to `BirthdayCard`.
+fn upper_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// `BirthdayCard` to `birthday_card`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !snake.is_empty() {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Builder can't be derived for generic structs",
+        ));
+    }
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Builder can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "Builder needs a struct with named fields",
+        ));
+    };
+    let fields: Vec<Field> = named
+        .named
+        .iter()
+        .map(|field| Field {
+            name: field.ident.as_ref().unwrap(),
+            ty: &field.ty,
+            optional: option_inner(&field.ty),
+        })
+        .collect();
+
+    let vis = &input.vis;
+    let target = &input.ident;
+    let builder = format_ident!("{target}Builder");
+    let states = format_ident!("{}", snake_case(&builder.to_string()));
+    let required: Vec<(&Field, State)> = fields
+        .iter()
+        .filter(|field| field.optional.is_none())
+        .map(|field| {
+            // `r#type` becomes `TypeState`, not `R#typeState`.
+            let camel = upper_camel_case(&field.name.unraw().to_string());
+            let state = State {
+                param: format_ident!("{camel}State"),
+                set: format_ident!("{camel}Set"),
+                unset: format_ident!("{camel}Unset"),
+            };
+            (field, state)
+        })
+        .collect();
+
+    let params: Vec<&Ident> = required.iter().map(|(_, state)| &state.param).collect();
+    let all_set: Vec<TokenStream2> = required
+        .iter()
+        .map(|(_, state)| {
+            let set = &state.set;
+            quote!(#states::#set)
+        })
+        .collect();
+    let all_unset: Vec<TokenStream2> = required
+        .iter()
+        .map(|(_, state)| {
+            let unset = &state.unset;
+            quote!(#states::#unset)
+        })
+        .collect();
+    let names: Vec<&Ident> = fields.iter().map(|field| field.name).collect();
+    let storage = fields.iter().map(|field| {
+        let ty = field.ty;
+        match field.optional {
+            Some(_) => quote!(#ty),
+            None => quote!(::std::option::Option<#ty>),
+        }
+    });
+
+    let markers = required.iter().map(|(field, state)| {
+        let (set, unset) = (&state.set, &state.unset);
+        let set_doc = format!("`{}` has been set.", field.name.unraw());
+        let unset_doc = format!("`{}` hasn't been set yet.", field.name.unraw());
+        quote! {
+            #[doc = #set_doc]
+            pub struct #set;
+            #[doc = #unset_doc]
+            pub struct #unset;
+        }
+    });
+
+    let required_setters = required.iter().enumerate().map(|(i, (field, state))| {
+        let (name, ty) = (field.name, field.ty);
+        let others = params
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, param)| param);
+        // The builder's type, with this field's state being `marker`.
+        let with = |marker: &Ident| -> Vec<TokenStream2> {
+            params
+                .iter()
+                .enumerate()
+                .map(|(j, param)| {
+                    if i == j {
+                        quote!(#states::#marker)
+                    } else {
+                        quote!(#param)
+                    }
+                })
+                .collect()
+        };
+        let before = with(&state.unset);
+        let after = with(&state.set);
+        let rest = names.iter().filter(|other| *other != &name);
+        quote! {
+            impl<#(#others),*> #builder<#(#before),*> {
+                pub fn #name(&mut self, #name: impl ::std::convert::Into<#ty>) -> #builder<#(#after),*> {
+                    #builder {
+                        #name: ::std::option::Option::Some(#name.into()),
+                        #(#rest: ::std::clone::Clone::clone(&self.#rest),)*
+                        _state: ::std::marker::PhantomData,
+                    }
+                }
+            }
+        }
+    });
+
+    let optional_setters = fields.iter().filter_map(|field| {
+        let (name, inner) = (field.name, field.optional?);
+        Some(quote! {
+            pub fn #name(&mut self, #name: impl ::std::convert::Into<#inner>) -> &mut Self {
+                self.#name = ::std::option::Option::Some(#name.into());
+                self
+            }
+        })
+    });
+
+    let built_fields = fields.iter().map(|field| {
+        let name = field.name;
+        match field.optional {
+            Some(_) => quote!(#name: ::std::clone::Clone::clone(&self.#name)),
+            None => quote! {
+                #name: ::std::clone::Clone::clone(&self.#name)
+                    .expect("the typestate ensures required fields are set")
+            },
+        }
+    });
+
+    let builder_doc = format!("Builds a [`{target}`]. Generated by `#[derive(Builder)]`.");
+    let states_doc = format!("Whether each required field of a [`{builder}`] has been set.");
+    Ok(quote! {
+        #[doc = #states_doc]
+        #vis mod #states {
+            #(#markers)*
+        }
+
+        #[doc = #builder_doc]
+        #vis struct #builder<#(#params),*> {
+            #(#names: #storage,)*
+            _state: ::std::marker::PhantomData<(#(#params,)*)>,
+        }
+
+        impl ::std::default::Default for #builder<#(#all_unset),*> {
+            fn default() -> Self {
+                Self {
+                    #(#names: ::std::option::Option::None,)*
+                    _state: ::std::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #builder<#(#all_unset),*> {
+            pub fn new() -> Self {
+                ::std::default::Default::default()
+            }
+        }
+
+        #(#required_setters)*
+
+        impl<#(#params),*> #builder<#(#params),*> {
+            #(#optional_setters)*
+        }
+
+        impl #builder<#(#all_set),*> {
+            pub fn build(&self) -> #target {
+                #target {
+                    #(#built_fields,)*
+                }
+            }
+        }
+
+        impl #target {
+            pub fn builder() -> #builder<#(#all_unset),*> {
+                #builder::new()
+            }
+        }
+    })
+}
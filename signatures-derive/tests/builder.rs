@@ -0,0 +1,137 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The book's `BirthdayCard`, with its builder derived rather than written
+//! out by hand.
+
+use signatures_derive::Builder;
+
+#[derive(Builder, Clone, Debug, PartialEq)]
+pub struct BirthdayCard {
+    name: String,
+    age: Option<i32>,
+    text: String,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+struct Settings {
+    verbose: Option<bool>,
+    retries: Option<u8>,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+struct Token {
+    r#type: String,
+    r#ref: Option<u32>,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn builds_the_book_example() {
+    let card = BirthdayCardBuilder::new()
+        .name("Paul")
+        .text("Happy Valentine's Day!")
+        .age(64)
+        .build();
+    assert_eq!(
+        card,
+        BirthdayCard {
+            name: "Paul".to_owned(),
+            age: Some(64),
+            text: "Happy Valentine's Day!".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn required_fields_can_be_set_in_any_order() {
+    let card = BirthdayCard::builder()
+        .text("Happy birthday!")
+        .name(String::from("Ringo"))
+        .build();
+    assert_eq!(card.name, "Ringo");
+    assert_eq!(card.age, None);
+}
+
+#[test]
+fn optional_setters_take_mut_self() {
+    let mut builder = BirthdayCard::builder().name("George").text("Hi");
+    let before = builder.build();
+    builder.age(58);
+    let after = builder.build();
+    assert_eq!(before.age, None);
+    assert_eq!(after.age, Some(58));
+}
+
+#[test]
+fn required_setters_take_mut_self() {
+    // So they can follow an optional setter, too.
+    let card = BirthdayCard::builder()
+        .name("John")
+        .age(40)
+        .text("Imagine")
+        .build();
+    assert_eq!(card.age, Some(40));
+
+    // The builder they're called on is left as it was.
+    let mut named = BirthdayCard::builder().name("Paul");
+    let yesterday = named.text("Yesterday").build();
+    let tomorrow = named.text("Tomorrow").build();
+    assert_eq!(yesterday.text, "Yesterday");
+    assert_eq!(tomorrow.text, "Tomorrow");
+    assert_eq!(tomorrow.name, "Paul");
+}
+
+#[test]
+fn raw_identifiers_are_fields_too() {
+    let token = TokenBuilder::new().r#type("keyword").r#ref(7u32).build();
+    assert_eq!(
+        token,
+        Token {
+            r#type: "keyword".to_owned(),
+            r#ref: Some(7),
+        }
+    );
+    // Its marker types are named after `type`, not `r#type`.
+    let _: token_builder::TypeSet;
+}
+
+#[test]
+fn all_optional_or_all_required() {
+    let settings = SettingsBuilder::new().retries(3).build();
+    assert_eq!(
+        settings,
+        Settings {
+            verbose: None,
+            retries: Some(3),
+        }
+    );
+    assert_eq!(
+        PointBuilder::default().y(2).x(1).build(),
+        Point { x: 1, y: 2 }
+    );
+}
+
+#[test]
+fn missing_or_repeated_fields_dont_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/missing_text.rs");
+    cases.compile_fail("tests/ui/name_twice.rs");
+    cases.compile_fail("tests/ui/not_a_struct.rs");
+}
@@ -0,0 +1,203 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A working version of the `BirthdayCardBuilder` from
+//! [How do I overload constructors?](https://cppfaq.rs/signatures.html#how-do-i-overload-constructors)
+//!
+//! There are two builders, so that the trade-off in the
+//! [API guidelines](https://rust-lang.github.io/api-guidelines/type-safety.html#non-consuming-builders-preferred)
+//! can be seen side by side:
+//!
+//! * [`BirthdayCardBuilder`] takes `&mut self`. It can be configured over
+//!   several statements, and reused to build several cards, but `build` has
+//!   to clone what it's been given.
+//! * [`ConsumingBirthdayCardBuilder`] takes `self`. `build` can move its
+//!   fields into the card, but configuring it conditionally means
+//!   reassigning it: `builder = builder.age(64)`.
+//!
+//! Either way, `build` checks the card makes sense and returns a
+//! [`BuildError`] if not.
+
+use std::fmt;
+
+const DEFAULT_TEXT: &str = "Happy birthday!";
+
+/// A finished birthday card.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BirthdayCard {
+    name: String,
+    age: Option<u32>,
+    text: String,
+    message: String,
+}
+
+impl BirthdayCard {
+    /// The checks shared by all the builders, including the one in
+    /// [`typestate`](crate::typestate).
+    pub(crate) fn new(
+        name: String,
+        age: Option<i32>,
+        text: Option<String>,
+    ) -> Result<Self, BuildError> {
+        if name.trim().is_empty() {
+            return Err(BuildError::EmptyName);
+        }
+        let age = age
+            .map(|age| u32::try_from(age).map_err(|_| BuildError::NegativeAge(age)))
+            .transpose()?;
+        let text = text.unwrap_or_else(|| DEFAULT_TEXT.to_owned());
+        let message = match age {
+            Some(age) => format!("Dear {name},\n{text}\nYou're {age} today!"),
+            None => format!("Dear {name},\n{text}"),
+        };
+        Ok(Self {
+            name,
+            age,
+            text,
+            message,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn age(&self) -> Option<u32> {
+        self.age
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The whole card, as it'll be written.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for BirthdayCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Why a card couldn't be built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// The name was empty, or only whitespace.
+    EmptyName,
+    NegativeAge(i32),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::EmptyName => f.write_str("a birthday card needs a name"),
+            BuildError::NegativeAge(age) => write!(f, "nobody is {age} years old"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Builds a [`BirthdayCard`] through `&mut self` methods, as the book
+/// recommends.
+///
+/// ```
+/// # use signatures::birthday::BirthdayCardBuilder;
+/// let card = BirthdayCardBuilder::new("Paul")
+///     .age(64)
+///     .text("Happy Valentine's Day!")
+///     .build()?;
+/// assert_eq!(card.age(), Some(64));
+/// # Ok::<(), signatures::birthday::BuildError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct BirthdayCardBuilder {
+    name: String,
+    age: Option<i32>,
+    text: Option<String>,
+}
+
+impl BirthdayCardBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            age: None,
+            text: None,
+        }
+    }
+
+    pub fn age(&mut self, age: i32) -> &mut Self {
+        self.age = Some(age);
+        self
+    }
+
+    /// Replaces the default "Happy birthday!".
+    pub fn text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Builds a card from the current settings. The builder is left as it
+    /// was, so it can build more.
+    pub fn build(&self) -> Result<BirthdayCard, BuildError> {
+        BirthdayCard::new(self.name.clone(), self.age, self.text.clone())
+    }
+}
+
+/// Builds a [`BirthdayCard`] through `self` methods, so nothing is cloned.
+///
+/// ```
+/// # use signatures::birthday::ConsumingBirthdayCardBuilder;
+/// let mut builder = ConsumingBirthdayCardBuilder::new("Paul");
+/// let know_age = true;
+/// if know_age {
+///     builder = builder.age(64);
+/// }
+/// let card = builder.build()?;
+/// # Ok::<(), signatures::birthday::BuildError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConsumingBirthdayCardBuilder {
+    name: String,
+    age: Option<i32>,
+    text: Option<String>,
+}
+
+impl ConsumingBirthdayCardBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            age: None,
+            text: None,
+        }
+    }
+
+    pub fn age(mut self, age: i32) -> Self {
+        self.age = Some(age);
+        self
+    }
+
+    /// Replaces the default "Happy birthday!".
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    pub fn build(self) -> Result<BirthdayCard, BuildError> {
+        BirthdayCard::new(self.name, self.age, self.text)
+    }
+}
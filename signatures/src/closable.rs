@@ -0,0 +1,103 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A file which is closed with [`ClosableFile::close`], an example for
+//! [Should I ever take `self` by value?](https://cppfaq.rs/signatures.html#should-i-ever-take-self-by-value)
+//!
+//! Dropping a `File`, or a `BufWriter`, flushes what it can and silently
+//! throws away any error, because `drop` can't return one. `close` takes
+//! `self` by value, so it can flush and sync and tell the caller whether
+//! that worked, and afterwards there's no file left to misuse. If the
+//! caller forgets to call it, `Drop` does the same work but can only log
+//! what went wrong.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// A writer which can be made durable, such as a [`File`]. Tests implement
+/// it for writers which fail on demand.
+pub trait Durable: Write {
+    /// Makes sure everything written so far has reached the disk.
+    fn sync(&mut self) -> io::Result<()>;
+}
+
+impl Durable for File {
+    fn sync(&mut self) -> io::Result<()> {
+        self.sync_all()
+    }
+}
+
+/// A buffered file which reports errors when it's closed.
+pub struct ClosableFile<W: Durable = File> {
+    /// Only `None` once `close` or `drop` has taken it.
+    writer: Option<BufWriter<W>>,
+}
+
+impl ClosableFile<File> {
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(File::create(path)?))
+    }
+}
+
+impl<W: Durable> ClosableFile<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            writer: Some(BufWriter::new(inner)),
+        }
+    }
+
+    /// Flushes and syncs the file. After this, the file's gone, whatever
+    /// the result.
+    pub fn close(mut self) -> io::Result<()> {
+        finish(
+            self.writer
+                .take()
+                .expect("only close and drop take the writer"),
+        )
+    }
+
+    fn writer(&mut self) -> &mut BufWriter<W> {
+        self.writer
+            .as_mut()
+            .expect("only close and drop take the writer")
+    }
+}
+
+fn finish<W: Durable>(writer: BufWriter<W>) -> io::Result<()> {
+    let mut inner = writer
+        .into_inner()
+        .map_err(io::IntoInnerError::into_error)?;
+    inner.sync()
+}
+
+impl<W: Durable> Write for ClosableFile<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+impl<W: Durable> Drop for ClosableFile<W> {
+    fn drop(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            if let Err(e) = finish(writer) {
+                log::error!("error closing a ClosableFile which wasn't closed explicitly: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Companion code for
+//! [Questions about your function signatures](https://cppfaq.rs/signatures.html).
+//! [`params`] has the book's `a`, `b` and `c`, and a couple more ways to
+//! accept a list of strings, so that it's possible to check which callers
+//! each of them suits. [`object_safety`] shows what `impl AsRef<str>` does to
+//! a trait. [`birthday`] has working versions of the book's builders, and
+//! [`typestate`] one which can't be asked to build too early. [`closable`]
+//! takes `self` by value to close a file, and [`token`] takes a token by
+//! value so that it can't be used twice.
+
+pub mod birthday;
+pub mod closable;
+pub mod object_safety;
+pub mod params;
+pub mod token;
+pub mod typestate;
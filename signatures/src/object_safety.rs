@@ -0,0 +1,73 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The same API twice, to show that
+//! ["if you have lots of AsRef then nothing is object-safe"](https://cppfaq.rs/signatures.html#how-flexible-should-my-parameters-be).
+//!
+//! [`Greeter`] takes `&str`, so it can be used as `dyn Greeter`.
+//! [`AsRefGreeter`] takes `impl AsRef<str>`, which makes `greet` generic, and
+//! a vtable can't hold a generic method: `dyn AsRefGreeter` doesn't compile
+//! (`tests/ui/` has both cases). [`GreeterExt`] shows how to have both: the
+//! convenient generic method lives in an extension trait implemented for
+//! every `Greeter`, including `dyn Greeter`.
+
+/// Greets people by name. Dyn-compatible.
+pub trait Greeter {
+    fn greet(&self, name: &str) -> String;
+}
+
+/// Greets people by name, accepting anything string-like. Not
+/// dyn-compatible.
+pub trait AsRefGreeter {
+    fn greet(&self, name: impl AsRef<str>) -> String;
+}
+
+/// The flexible version of [`Greeter::greet`], for every `Greeter`.
+pub trait GreeterExt: Greeter {
+    fn greet_any(&self, name: impl AsRef<str>) -> String {
+        self.greet(name.as_ref())
+    }
+}
+
+impl<G: Greeter + ?Sized> GreeterExt for G {}
+
+/// Says hello.
+pub struct Polite;
+
+impl Greeter for Polite {
+    fn greet(&self, name: &str) -> String {
+        format!("Hello, {name}.")
+    }
+}
+
+impl AsRefGreeter for Polite {
+    fn greet(&self, name: impl AsRef<str>) -> String {
+        format!("Hello, {}.", name.as_ref())
+    }
+}
+
+/// Says hi.
+pub struct Casual;
+
+impl Greeter for Casual {
+    fn greet(&self, name: &str) -> String {
+        format!("Hi {name}!")
+    }
+}
+
+impl AsRefGreeter for Casual {
+    fn greet(&self, name: impl AsRef<str>) -> String {
+        format!("Hi {}!", name.as_ref())
+    }
+}
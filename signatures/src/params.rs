@@ -0,0 +1,61 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Five ways to accept a list of strings, from
+//! [How flexible should my parameters be?](https://cppfaq.rs/signatures.html#how-flexible-should-my-parameters-be)
+//! Each joins its parameters with spaces, so there is something to test.
+//!
+//! `tests/params.rs` tries calling each of them with each of the usual
+//! arguments, and checks the results against `compatibility.md`, which the
+//! book includes.
+//!
+//! The tests compile this file on its own, so it mustn't use anything from
+//! the rest of the crate.
+
+use std::borrow::Cow;
+
+/// The book's `a`: only for callers who have `String`s.
+pub fn strings(params: &[String]) -> String {
+    params.join(" ")
+}
+
+/// The book's `b`, and its recommendation.
+pub fn strs(params: &[&str]) -> String {
+    params.join(" ")
+}
+
+/// The book's `c`: any slice of string-like things, but an empty one has no
+/// type to infer.
+pub fn as_refs(params: &[impl AsRef<str>]) -> String {
+    let params: Vec<&str> = params.iter().map(AsRef::as_ref).collect();
+    params.join(" ")
+}
+
+/// Accepts any collection, not just slices, whether it's borrowed or not.
+pub fn into_iter(params: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    let mut joined = String::new();
+    for param in params {
+        if !joined.is_empty() {
+            joined.push(' ');
+        }
+        joined.push_str(param.as_ref());
+    }
+    joined
+}
+
+/// Both owned and borrowed strings, in the same slice, but callers rarely
+/// have a slice of `Cow`s to hand.
+pub fn cows(params: &[Cow<str>]) -> String {
+    params.join(" ")
+}
@@ -0,0 +1,139 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A token which can only be used once, for
+//! [When should I take parameters by value?](https://cppfaq.rs/signatures.html#when-should-i-take-parameters-by-value)
+//!
+//! [`UniqueToken`] is neither `Clone` nor `Copy`, and nothing outside this
+//! module can make one except a [`TokenGenerator`]. So once a token has been
+//! passed by value to [`Registry::register`], the caller no longer has it:
+//! registering it twice, or with two registries, doesn't compile
+//! (`tests/ui/` has the cases). What callers keep instead is a [`TokenId`],
+//! which names the token but can't stand in for it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A unique value, like a UUID, which can be used exactly once.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UniqueToken(TokenId);
+
+impl UniqueToken {
+    pub fn id(&self) -> TokenId {
+        self.0
+    }
+}
+
+/// The name of a [`UniqueToken`], which can be copied freely. Displays like
+/// a UUID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenId(u128);
+
+impl fmt::Display for TokenId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = format!("{:032x}", self.0);
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
+
+/// Makes [`UniqueToken`]s. Tokens from different generators in the same
+/// process never collide, because each generator gets its own upper 64 bits.
+#[derive(Debug)]
+pub struct TokenGenerator {
+    generator: u64,
+    next: u64,
+}
+
+impl TokenGenerator {
+    pub fn new() -> Self {
+        static GENERATORS: AtomicU64 = AtomicU64::new(0);
+        Self {
+            generator: GENERATORS.fetch_add(1, Ordering::Relaxed),
+            next: 0,
+        }
+    }
+}
+
+impl Default for TokenGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for TokenGenerator {
+    type Item = UniqueToken;
+
+    fn next(&mut self) -> Option<UniqueToken> {
+        let token = UniqueToken(TokenId(
+            u128::from(self.generator) << 64 | u128::from(self.next),
+        ));
+        self.next = self.next.checked_add(1).expect("out of tokens");
+        Some(token)
+    }
+}
+
+/// Values registered against tokens. Registering consumes the token.
+#[derive(Debug)]
+pub struct Registry<T> {
+    entries: BTreeMap<TokenId, T>,
+}
+
+impl<T> Registry<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `value`, using up `token`. Returns the token's ID for
+    /// looking the value up later.
+    pub fn register(&mut self, token: UniqueToken, value: T) -> TokenId {
+        let id = token.id();
+        let previous = self.entries.insert(id, value);
+        // Can't happen: nobody else could have had this token.
+        assert!(previous.is_none(), "token {id} registered twice");
+        id
+    }
+
+    pub fn get(&self, id: TokenId) -> Option<&T> {
+        self.entries.get(&id)
+    }
+
+    pub fn remove(&mut self, id: TokenId) -> Option<T> {
+        self.entries.remove(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A [`BirthdayCardBuilder`] which won't build a card until it has both a
+//! name and some text. The builder's type parameters record what's been
+//! set so far, so calling `build` too early isn't a runtime error but a
+//! compile error:
+//!
+//! ```text
+//! no method named `build` found for struct `BirthdayCardBuilder<NameSet, TextUnset>`
+//! ```
+//!
+//! It's the same idea as making it
+//! ["compile-time impossible to act on the global state unless appropriate preconditions are met"](https://cppfaq.rs/codebase.html),
+//! applied to construction. Each setter consumes the builder and returns
+//! one of a different type, so unlike [`crate::birthday::BirthdayCardBuilder`]
+//! this can't be configured through `&mut self`. The state types carry the
+//! values themselves, so there's nothing to unwrap. `build` still returns a
+//! `Result`: the types can insist that there's a name, but not that it's
+//! a sensible one.
+
+use crate::birthday::{BirthdayCard, BuildError};
+
+/// No name yet.
+pub struct NameUnset;
+
+/// Has a name.
+pub struct NameSet(String);
+
+/// No text yet.
+pub struct TextUnset;
+
+/// Has some text.
+pub struct TextSet(String);
+
+/// Builds a [`BirthdayCard`], once it has a name and some text.
+///
+/// ```
+/// # use signatures::typestate::BirthdayCardBuilder;
+/// let card = BirthdayCardBuilder::new()
+///     .name("Paul")
+///     .age(64)
+///     .text("Happy Valentine's Day!")
+///     .build()?;
+/// # Ok::<(), signatures::birthday::BuildError>(())
+/// ```
+pub struct BirthdayCardBuilder<Name, Text> {
+    name: Name,
+    age: Option<i32>,
+    text: Text,
+}
+
+impl BirthdayCardBuilder<NameUnset, TextUnset> {
+    pub fn new() -> Self {
+        Self {
+            name: NameUnset,
+            age: None,
+            text: TextUnset,
+        }
+    }
+}
+
+impl Default for BirthdayCardBuilder<NameUnset, TextUnset> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Text> BirthdayCardBuilder<NameUnset, Text> {
+    pub fn name(self, name: &str) -> BirthdayCardBuilder<NameSet, Text> {
+        BirthdayCardBuilder {
+            name: NameSet(name.to_owned()),
+            age: self.age,
+            text: self.text,
+        }
+    }
+}
+
+impl<Name> BirthdayCardBuilder<Name, TextUnset> {
+    pub fn text(self, text: &str) -> BirthdayCardBuilder<Name, TextSet> {
+        BirthdayCardBuilder {
+            name: self.name,
+            age: self.age,
+            text: TextSet(text.to_owned()),
+        }
+    }
+}
+
+impl<Name, Text> BirthdayCardBuilder<Name, Text> {
+    /// Optional, so this can be called in any state.
+    pub fn age(mut self, age: i32) -> Self {
+        self.age = Some(age);
+        self
+    }
+}
+
+impl BirthdayCardBuilder<NameSet, TextSet> {
+    pub fn build(self) -> Result<BirthdayCard, BuildError> {
+        BirthdayCard::new(self.name.0, self.age, Some(self.text.0))
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use signatures::birthday::{BirthdayCardBuilder, BuildError, ConsumingBirthdayCardBuilder};
+
+#[test]
+fn builds_a_card() {
+    let card = BirthdayCardBuilder::new("Paul")
+        .age(64)
+        .text("Happy Valentine's Day!")
+        .build()
+        .unwrap();
+    assert_eq!(card.name(), "Paul");
+    assert_eq!(card.age(), Some(64));
+    assert_eq!(card.text(), "Happy Valentine's Day!");
+    assert_eq!(
+        card.to_string(),
+        "Dear Paul,\nHappy Valentine's Day!\nYou're 64 today!"
+    );
+}
+
+#[test]
+fn defaults() {
+    let card = BirthdayCardBuilder::new("Ringo").build().unwrap();
+    assert_eq!(card.age(), None);
+    assert_eq!(card.message(), "Dear Ringo,\nHappy birthday!");
+}
+
+#[test]
+fn rejects_nonsense() {
+    assert_eq!(
+        BirthdayCardBuilder::new("  ").build(),
+        Err(BuildError::EmptyName)
+    );
+    assert_eq!(
+        BirthdayCardBuilder::new("Paul").age(-1).build(),
+        Err(BuildError::NegativeAge(-1))
+    );
+    assert_eq!(
+        ConsumingBirthdayCardBuilder::new("").age(-1).build(),
+        Err(BuildError::EmptyName)
+    );
+    assert_eq!(
+        BuildError::NegativeAge(-3).to_string(),
+        "nobody is -3 years old"
+    );
+}
+
+#[test]
+fn mut_builder_can_be_reused() {
+    let mut builder = BirthdayCardBuilder::new("George");
+    let first = builder.build().unwrap();
+    builder.age(58);
+    let second = builder.build().unwrap();
+    assert_eq!(first.age(), None);
+    assert_eq!(second.age(), Some(58));
+    assert_eq!(builder.age(-5).build(), Err(BuildError::NegativeAge(-5)));
+}
+
+#[test]
+fn both_builders_agree() {
+    let by_ref = BirthdayCardBuilder::new("John")
+        .age(40)
+        .text("Imagine")
+        .build();
+    let by_value = ConsumingBirthdayCardBuilder::new(String::from("John"))
+        .age(40)
+        .text("Imagine")
+        .build();
+    assert_eq!(by_ref, by_value);
+}
+
+#[test]
+fn mut_builder_takes_owned_strings() {
+    let name = String::from("Ringo");
+    let text = format!("Happy {}th birthday!", 30);
+    let card = BirthdayCardBuilder::new(name).text(text).build().unwrap();
+    assert_eq!(card.name(), "Ringo");
+    assert_eq!(card.text(), "Happy 30th birthday!");
+}
@@ -0,0 +1,168 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, Once};
+
+use signatures::closable::{ClosableFile, Durable};
+
+/// Somewhere in memory if we can, so the tests don't wait for a real disk.
+fn temp_path(name: &str) -> PathBuf {
+    let shm = PathBuf::from("/dev/shm");
+    let dir = if shm.is_dir() { shm } else { env::temp_dir() };
+    dir.join(format!("closable-{}-{name}", std::process::id()))
+}
+
+/// Where in its life a [`FailingWriter`] should fail.
+#[derive(Clone, Copy, PartialEq)]
+enum Stage {
+    Write,
+    Sync,
+}
+
+/// Accepts writes until it reaches `fail_at`.
+struct FailingWriter {
+    fail_at: Stage,
+    message: &'static str,
+}
+
+impl FailingWriter {
+    fn error(&self) -> io::Error {
+        io::Error::other(self.message)
+    }
+}
+
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.fail_at {
+            Stage::Write => Err(self.error()),
+            Stage::Sync => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Durable for FailingWriter {
+    fn sync(&mut self) -> io::Result<()> {
+        Err(self.error())
+    }
+}
+
+/// Collects everything logged, from every test.
+struct Logger;
+
+static LOGGED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        LOGGED.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+fn start_logging() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&Logger).unwrap();
+        log::set_max_level(log::LevelFilter::Error);
+    });
+}
+
+fn logged(text: &str) -> bool {
+    LOGGED
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|line| line.contains(text))
+}
+
+#[test]
+fn close_writes_everything() {
+    let path = temp_path("ok");
+    let mut file = ClosableFile::create(&path).unwrap();
+    writeln!(file, "Dear Paul,").unwrap();
+    write!(file, "Happy birthday!").unwrap();
+    file.close().unwrap();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "Dear Paul,\nHappy birthday!"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn drop_writes_everything_too() {
+    let path = temp_path("drop");
+    let mut file = ClosableFile::create(&path).unwrap();
+    write!(file, "Forgot to close").unwrap();
+    drop(file);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "Forgot to close");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn close_reports_flush_errors() {
+    start_logging();
+    let mut file = ClosableFile::new(FailingWriter {
+        fail_at: Stage::Write,
+        message: "disk full on flush",
+    });
+    // Buffered, so this can't fail yet.
+    write!(file, "Hello").unwrap();
+    let error = file.close().unwrap_err();
+    assert_eq!(error.to_string(), "disk full on flush");
+    // The caller heard about it, so it isn't logged as well.
+    assert!(!logged("disk full on flush"));
+}
+
+#[test]
+fn close_reports_sync_errors() {
+    let file = ClosableFile::new(FailingWriter {
+        fail_at: Stage::Sync,
+        message: "sync failed on close",
+    });
+    assert_eq!(
+        file.close().unwrap_err().to_string(),
+        "sync failed on close"
+    );
+}
+
+#[test]
+fn drop_logs_what_it_swallows() {
+    start_logging();
+    let mut file = ClosableFile::new(FailingWriter {
+        fail_at: Stage::Write,
+        message: "disk full on drop",
+    });
+    write!(file, "Hello").unwrap();
+    drop(file);
+    assert!(logged("disk full on drop"));
+
+    drop(ClosableFile::new(FailingWriter {
+        fail_at: Stage::Sync,
+        message: "sync failed on drop",
+    }));
+    assert!(logged("sync failed on drop"));
+}
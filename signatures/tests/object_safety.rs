@@ -0,0 +1,37 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use signatures::object_safety::{AsRefGreeter, Casual, Greeter, GreeterExt, Polite};
+
+#[test]
+fn dyn_compatibility() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/dyn_str.rs");
+    cases.compile_fail("tests/ui/dyn_as_ref.rs");
+}
+
+#[test]
+fn both_apis_greet_alike() {
+    let name = String::from("Adrian");
+    assert_eq!(Greeter::greet(&Polite, &name), "Hello, Adrian.");
+    assert_eq!(AsRefGreeter::greet(&Polite, &name), "Hello, Adrian.");
+    assert_eq!(Greeter::greet(&Casual, "Adrian"), "Hi Adrian!");
+    assert_eq!(AsRefGreeter::greet(&Casual, "Adrian"), "Hi Adrian!");
+}
+
+#[test]
+fn extension_trait_works_through_dyn() {
+    let greeter: Box<dyn Greeter> = Box::new(Casual);
+    assert_eq!(greeter.greet_any(String::from("Martin")), "Hi Martin!");
+}
@@ -0,0 +1,133 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Which arguments each of the signatures in `params` accepts. Each
+//! combination is compiled separately with rustc, and the results are
+//! written up as the table in `compatibility.md`. Run with `BLESS=1` to
+//! update the table after changing the matrix.
+
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+use signatures::params::{as_refs, cows, into_iter, strings, strs};
+
+/// The functions in `params`, and their parameter types.
+const FUNCTIONS: [(&str, &str); 5] = [
+    ("strings", "&[String]"),
+    ("strs", "&[&str]"),
+    ("as_refs", "&[impl AsRef<str>]"),
+    ("into_iter", "impl IntoIterator<Item = impl AsRef<str>>"),
+    ("cows", "&[Cow<str>]"),
+];
+
+/// What a caller might have, and how they'd pass it.
+const ARGUMENTS: [(&str, &str); 5] = [
+    ("empty array", "&[]"),
+    ("string literals", r#"&["hi", "there"]"#),
+    ("`Vec<String>`", r#"&vec![String::from("hi")]"#),
+    ("`Vec<&str>`", r#"&vec!["hi"]"#),
+    (
+        "`Box<[String]>`",
+        r#"&vec![String::from("hi")].into_boxed_slice()"#,
+    ),
+];
+
+/// Compiles a call to `function` with `argument`, returning the error code
+/// if it doesn't compile.
+fn compile(dir: &Path, function: &str, argument: &str) -> Result<(), String> {
+    let params = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/params.rs");
+    let source = dir.join(format!("{function}.rs"));
+    fs::write(
+        &source,
+        format!(
+            "#[path = {params:?}]\nmod params;\n\nfn main() {{\n    params::{function}({argument});\n}}\n"
+        ),
+    )
+    .unwrap();
+    let output = Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+        .args(["--edition=2021", "--emit=metadata", "--error-format=json"])
+        .arg("--out-dir")
+        .arg(dir)
+        .arg(&source)
+        .output()
+        .unwrap();
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let code = stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|message| message["level"] == "error")
+        .find_map(|message| message["code"]["code"].as_str().map(str::to_owned));
+    Err(code.unwrap_or_else(|| panic!("{function}({argument}) failed oddly:\n{stderr}")))
+}
+
+fn compatibility_table() -> String {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("params");
+    fs::create_dir_all(&dir).unwrap();
+    let mut table = String::from("| Caller has | Argument |");
+    for (_, parameter) in FUNCTIONS {
+        table.push_str(&format!(" `{parameter}` |"));
+    }
+    table.push_str("\n|---|---|");
+    table.push_str(&"---|".repeat(FUNCTIONS.len()));
+    table.push('\n');
+    for (description, argument) in ARGUMENTS {
+        table.push_str(&format!("| {description} | `{argument}` |"));
+        for (function, _) in FUNCTIONS {
+            match compile(&dir, function, argument) {
+                Ok(()) => table.push_str(" ✓ |"),
+                Err(code) => table.push_str(&format!(" {code} |")),
+            }
+        }
+        table.push('\n');
+    }
+    table
+}
+
+#[test]
+fn compatibility_table_is_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("compatibility.md");
+    let table = compatibility_table();
+    if env::var_os("BLESS").is_some() {
+        fs::write(&path, table).unwrap();
+    } else {
+        let expected = fs::read_to_string(&path).unwrap_or_default();
+        assert_eq!(
+            table, expected,
+            "compatibility.md is out of date; run with BLESS=1 to update it"
+        );
+    }
+}
+
+#[test]
+fn all_join_their_parameters() {
+    let owned = vec![String::from("hi"), String::from("there")];
+    let borrowed = ["hi", "there"];
+    let cowed = [Cow::Borrowed("hi"), Cow::Owned(String::from("there"))];
+    assert_eq!(strings(&owned), "hi there");
+    assert_eq!(strs(&borrowed), "hi there");
+    assert_eq!(as_refs(&owned), "hi there");
+    assert_eq!(as_refs(&borrowed), "hi there");
+    assert_eq!(into_iter(&owned), "hi there");
+    assert_eq!(into_iter(borrowed), "hi there");
+    assert_eq!(into_iter(Vec::<String>::new()), "");
+    assert_eq!(cows(&cowed), "hi there");
+    assert_eq!(strs(&[]), "");
+}
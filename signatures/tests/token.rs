@@ -0,0 +1,54 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use signatures::token::{Registry, TokenGenerator};
+
+#[test]
+fn tokens_are_unique() {
+    let mut ids = HashSet::new();
+    for generator in [TokenGenerator::new(), TokenGenerator::new()] {
+        for token in generator.take(100) {
+            assert!(ids.insert(token.id()));
+        }
+    }
+}
+
+#[test]
+fn ids_look_like_uuids() {
+    let id = TokenGenerator::new().next().unwrap().id().to_string();
+    let groups: Vec<usize> = id.split('-').map(str::len).collect();
+    assert_eq!(groups, [8, 4, 4, 4, 12]);
+}
+
+#[test]
+fn registry_looks_up_by_id() {
+    let mut tokens = TokenGenerator::new();
+    let mut registry = Registry::new();
+    assert!(registry.is_empty());
+    let paul = registry.register(tokens.next().unwrap(), "Paul");
+    let ringo = registry.register(tokens.next().unwrap(), "Ringo");
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.get(paul), Some(&"Paul"));
+    assert_eq!(registry.remove(ringo), Some("Ringo"));
+    assert_eq!(registry.get(ringo), None);
+}
+
+#[test]
+fn tokens_cant_be_reused() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/register_twice.rs");
+    cases.compile_fail("tests/ui/clone_token.rs");
+}
@@ -0,0 +1,42 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use signatures::birthday::BuildError;
+use signatures::typestate::BirthdayCardBuilder;
+
+#[test]
+fn builds_once_everything_is_set() {
+    let card = BirthdayCardBuilder::new()
+        .text("Happy Valentine's Day!")
+        .age(64)
+        .name("Paul")
+        .build()
+        .unwrap();
+    assert_eq!(card.name(), "Paul");
+    assert_eq!(card.age(), Some(64));
+    assert_eq!(card.text(), "Happy Valentine's Day!");
+}
+
+#[test]
+fn still_checks_values() {
+    let result = BirthdayCardBuilder::default().name("").text("Hi").build();
+    assert_eq!(result, Err(BuildError::EmptyName));
+}
+
+#[test]
+fn missing_fields_dont_compile() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/build_without_text.rs");
+    cases.compile_fail("tests/ui/text_twice.rs");
+}
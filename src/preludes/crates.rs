@@ -0,0 +1,22 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The crates from tools/book-deps which code blocks may use. Test the book
+// with `cargo run -p book-deps`, which builds them, rather than `mdbook test`.
+extern crate crossbeam_utils;
+extern crate cxx;
+extern crate once_cell;
+extern crate petgraph;
+extern crate rayon;
+extern crate slotmap;
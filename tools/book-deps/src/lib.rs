@@ -0,0 +1,34 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The third-party crates which the book's code blocks are allowed to use.
+//!
+//! `mdbook test` has no notion of dependencies: all it can do is pass `-L`
+//! directories on to rustdoc. So this package depends on every crate the book
+//! recommends, and its binary builds them and hands the results to
+//! `mdbook test`:
+//!
+//! ```text
+//! cargo run -p book-deps                 # instead of `mdbook test`
+//! cargo run -p book-deps -- -c code      # any extra arguments go to mdbook
+//! ```
+//!
+//! Code blocks declare the crates with `prelude=crates`, which adds the
+//! `extern crate` lines that rustdoc needs to find them. Nothing is fetched
+//! beyond what `Cargo.lock` pins, so once `cargo fetch` has run the book can
+//! be tested with `--offline` (or `CARGO_NET_OFFLINE=true`).
+//!
+//! To let the book use another crate, add it to `[dependencies]` here and to
+//! `src/preludes/crates.rs`. `extract-examples` copies these dependencies into
+//! the package it generates, so the crates are available there too.
@@ -0,0 +1,111 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builds the crates the book uses and runs `mdbook test` against them.
+
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command, Stdio};
+
+use serde_json::Value;
+
+/// Builds this package's library, and returns every library file which went
+/// into it: the book's crates and everything they depend on.
+fn build(workspace: &Path) -> Result<Vec<PathBuf>, String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut child = Command::new(cargo)
+        .current_dir(workspace)
+        .args(["build", "--lib", "--package", "book-deps"])
+        .arg("--message-format=json-render-diagnostics")
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("unable to run cargo: {e}"))?;
+    let mut libraries = Vec::new();
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.map_err(|e| format!("unable to read cargo's output: {e}"))?;
+        let Ok(message) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        if message["reason"] != "compiler-artifact" {
+            continue;
+        }
+        let kinds = message["target"]["kind"].as_array().into_iter().flatten();
+        if !kinds
+            .filter_map(Value::as_str)
+            .any(|kind| kind.ends_with("lib") || kind == "proc-macro")
+        {
+            continue;
+        }
+        let filenames = message["filenames"].as_array().into_iter().flatten();
+        libraries.extend(
+            filenames
+                .filter_map(Value::as_str)
+                .filter(|name| !name.ends_with(".rmeta"))
+                .map(PathBuf::from),
+        );
+    }
+    let status = child
+        .wait()
+        .map_err(|e| format!("unable to run cargo: {e}"))?;
+    if !status.success() {
+        return Err("cargo failed to build the book's dependencies".to_owned());
+    }
+    Ok(libraries)
+}
+
+/// Copies `libraries` into a fresh `dir`. `target/debug/deps` itself may hold
+/// stale builds of the same crates, which rustc would refuse to choose between.
+fn collect(libraries: &[PathBuf], dir: &Path) -> Result<(), String> {
+    let io_error = |e: std::io::Error| format!("unable to write {}: {e}", dir.display());
+    if dir.exists() {
+        fs::remove_dir_all(dir).map_err(io_error)?;
+    }
+    fs::create_dir_all(dir).map_err(io_error)?;
+    for library in libraries {
+        let name = library
+            .file_name()
+            .ok_or_else(|| format!("{} isn't a file", library.display()))?;
+        fs::copy(library, dir.join(name))
+            .map_err(|e| format!("unable to copy {}: {e}", library.display()))?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let libraries = build(&workspace).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        exit(1);
+    });
+    let dir = workspace.join("target/book-deps");
+    if let Err(message) = collect(&libraries, &dir) {
+        eprintln!("{message}");
+        exit(1);
+    }
+    let mdbook = env::var_os("MDBOOK").unwrap_or_else(|| "mdbook".into());
+    let status = Command::new(mdbook)
+        .current_dir(&workspace)
+        .arg("test")
+        .arg("-L")
+        .arg(&dir)
+        .args(env::args_os().skip(1))
+        .status()
+        .unwrap_or_else(|e| {
+            eprintln!("unable to run mdbook: {e}");
+            exit(1);
+        });
+    exit(status.code().unwrap_or(1));
+}
@@ -0,0 +1,292 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shows what the compiler actually made of one of the book's example
+//! functions, so that claims like "functional pipelines simply don't require
+//! bounds checks" can be checked rather than taken on trust.
+//!
+//! ```text
+//! cargo run -p codegen -- make_shopping_list_a
+//! cargo run -p codegen -- --llvm-ir --package pets make_shopping_list_c
+//! ```
+//!
+//! The named function is compiled in release mode and its assembly (or LLVM
+//! IR) printed. Calls to `panic_bounds_check` and vector instructions are
+//! flagged and counted.
+//!
+//! Only packed arithmetic and comparisons inside one of the function's loops
+//! count as vectorization. Code inlined from `hashbrown` is left out, because
+//! its SSE2 group probing puts vector instructions in anything which touches
+//! a `HashSet`, whether or not the loop itself was vectorized. So in practice
+//! the SIMD count is only interesting for functions over `Vec`s and slices.
+//! The function is built with line tables so that inlined code can be traced
+//! back to its source.
+
+use std::collections::HashMap;
+
+/// What to ask rustc for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Emit {
+    Asm,
+    LlvmIr,
+}
+
+impl Emit {
+    /// The rustc flag which asks for it.
+    pub fn rustc_arg(self) -> &'static str {
+        match self {
+            Emit::Asm => "--emit=asm",
+            Emit::LlvmIr => "--emit=llvm-ir",
+        }
+    }
+
+    /// The extension of the file rustc writes it to.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Emit::Asm => "s",
+            Emit::LlvmIr => "ll",
+        }
+    }
+}
+
+/// Whether a mangled symbol is for a function called `function`. Legacy
+/// mangling writes each path segment as its length followed by its name.
+pub fn symbol_is(symbol: &str, function: &str) -> bool {
+    let segment = format!("{}{}", function.len(), function);
+    symbol
+        .match_indices(&segment)
+        .any(|(i, _)| !symbol[..i].ends_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Finds the body of `function` in assembly output: from its label to the
+/// matching `.Lfunc_end`.
+pub fn asm_body<'a>(output: &'a str, function: &str) -> Option<Vec<&'a str>> {
+    let mut lines = output.lines();
+    let label = lines.find(|line| {
+        !line.starts_with(char::is_whitespace)
+            && line
+                .strip_suffix(':')
+                .is_some_and(|symbol| symbol_is(symbol, function))
+    })?;
+    let mut body = vec![label];
+    body.extend(lines.take_while(|line| !line.trim_start_matches('.').starts_with("Lfunc_end")));
+    Some(body)
+}
+
+/// Finds the body of `function` in LLVM IR: from its `define` to the closing
+/// brace.
+pub fn llvm_ir_body<'a>(output: &'a str, function: &str) -> Option<Vec<&'a str>> {
+    let mut lines = output.lines();
+    let define = lines.find(|line| {
+        line.starts_with("define ")
+            && line
+                .split('@')
+                .nth(1)
+                .and_then(|rest| rest.split('(').next())
+                .is_some_and(|symbol| symbol_is(symbol, function))
+    })?;
+    let mut body = vec![define];
+    body.extend(lines.take_while(|line| *line != "}"));
+    body.push("}");
+    Some(body)
+}
+
+/// Whether `line` calls the panic handler for an out-of-bounds index.
+pub fn is_bounds_check(line: &str) -> bool {
+    line.contains("panic_bounds_check")
+}
+
+/// Whether `line` is packed (SIMD) arithmetic or a packed comparison. Moves,
+/// shuffles and bitwise operations don't count: they turn up in plenty of
+/// code which isn't vectorized.
+pub fn is_vector(line: &str, emit: Emit) -> bool {
+    let line = line.trim_start();
+    match emit {
+        Emit::Asm => {
+            let mut parts = line.splitn(2, char::is_whitespace);
+            let mnemonic = parts.next().unwrap_or_default();
+            let operands = parts.next().unwrap_or_default();
+            if mnemonic.starts_with('.') || mnemonic.ends_with(':') {
+                return false;
+            }
+            // SSE/AVX: packed integers are `p...`, packed floats `...ps`/`...pd`.
+            // The AVX forms have a leading `v`.
+            let x86 = mnemonic.strip_prefix('v').unwrap_or(mnemonic);
+            let packed_integer = ["padd", "psub", "pmul", "pcmp", "pmin", "pmax", "pmadd"]
+                .iter()
+                .any(|prefix| x86.starts_with(prefix));
+            let packed_float = ["add", "sub", "mul", "div", "min", "max", "cmp", "fmadd"]
+                .iter()
+                .any(|prefix| x86.starts_with(prefix))
+                && (x86.ends_with("ps") || x86.ends_with("pd"));
+            // NEON: ordinary mnemonics, but vector lanes in the operands.
+            let neon = [
+                "add", "sub", "mul", "cm", "fadd", "fsub", "fmul", "fcm", "umax", "umin", "smax",
+                "smin",
+            ]
+            .iter()
+            .any(|prefix| mnemonic.starts_with(prefix))
+                && [".16b", ".8h", ".4s", ".2d"]
+                    .iter()
+                    .any(|lanes| operands.contains(lanes));
+            packed_integer || packed_float || neon
+        }
+        // e.g. `%5 = add <4 x i32> %3, %4` or `%6 = icmp eq <16 x i8> ...`.
+        // Arrays like `[4 x i32]` don't count.
+        Emit::LlvmIr => {
+            let Some((_, instruction)) = line.split_once(" = ") else {
+                return false;
+            };
+            let mut words = instruction.split_whitespace();
+            let op = words.next().unwrap_or_default();
+            let arithmetic = [
+                "add", "sub", "mul", "udiv", "sdiv", "fadd", "fsub", "fmul", "fdiv", "icmp", "fcmp",
+            ];
+            arithmetic.contains(&op)
+                && instruction.split('<').skip(1).any(|rest| {
+                    let lanes =
+                        rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+                    lanes > 0 && rest[lanes..].starts_with(" x ")
+                })
+        }
+    }
+}
+
+/// The label a line defines, if any: `.LBB3_2:` in assembly, `bb2:` or `12:`
+/// in LLVM IR.
+pub fn label(line: &str) -> Option<&str> {
+    let line = line.split(';').next().unwrap_or_default().trim_end();
+    let label = line.strip_suffix(':')?;
+    (!label.is_empty() && !label.contains(char::is_whitespace)).then_some(label)
+}
+
+/// The labels a line may jump to.
+pub fn jump_targets(line: &str, emit: Emit) -> Vec<&str> {
+    let line = line.trim_start();
+    match emit {
+        Emit::Asm => {
+            let mut parts = line.split_whitespace();
+            let mnemonic = parts.next().unwrap_or_default();
+            let is_jump = (mnemonic.starts_with('j') && mnemonic != "jmpq")
+                || mnemonic == "b"
+                || mnemonic.starts_with("b.")
+                || ["cbz", "cbnz", "tbz", "tbnz"].contains(&mnemonic);
+            if is_jump {
+                parts
+                    .map(|operand| operand.trim_end_matches(','))
+                    .filter(|operand| operand.starts_with(".L"))
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        }
+        Emit::LlvmIr => {
+            if !line.starts_with("br ") {
+                return Vec::new();
+            }
+            line.split("label %")
+                .skip(1)
+                .filter_map(|rest| rest.split([',', ' ']).next())
+                .collect()
+        }
+    }
+}
+
+/// For each line of `body`, whether it's inside a loop: between a label and
+/// a later jump back to it.
+pub fn in_loops(body: &[&str], emit: Emit) -> Vec<bool> {
+    let labels: HashMap<&str, usize> = body
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((label(line)?, i)))
+        .collect();
+    let mut in_loop = vec![false; body.len()];
+    for (i, line) in body.iter().enumerate() {
+        for target in jump_targets(line, emit) {
+            if let Some(&start) = labels.get(target) {
+                if start <= i {
+                    in_loop[start..=i].iter_mut().for_each(|line| *line = true);
+                }
+            }
+        }
+    }
+    in_loop
+}
+
+/// The file each line of `body` was compiled from, which for inlined code is
+/// the file it was inlined from. `output` is the whole of rustc's output,
+/// which has the debug info `body` refers to.
+pub fn source_files(output: &str, body: &[&str], emit: Emit) -> Vec<Option<String>> {
+    match emit {
+        // `.file 3 "/dir" "name.rs"` declares a file, and `.loc 3 ...` says
+        // that what follows came from it.
+        Emit::Asm => {
+            let files: HashMap<&str, String> = output
+                .lines()
+                .filter_map(|line| {
+                    let rest = line.trim_start().strip_prefix(".file")?.trim_start();
+                    let (number, rest) = rest.split_once(char::is_whitespace)?;
+                    let parts: Vec<&str> = rest.split('"').skip(1).step_by(2).collect();
+                    Some((number, parts.join("/")))
+                })
+                .collect();
+            let mut current = None;
+            body.iter()
+                .map(|line| {
+                    if let Some(rest) = line.trim_start().strip_prefix(".loc") {
+                        let number = rest.split_whitespace().next().unwrap_or_default();
+                        current = files.get(number).cloned();
+                    }
+                    current.clone()
+                })
+                .collect()
+        }
+        // `!dbg !12` names a `DILocation`, whose `scope:` has a `file:`,
+        // which is a `DIFile`.
+        Emit::LlvmIr => {
+            let metadata: HashMap<&str, &str> = output
+                .lines()
+                .filter_map(|line| {
+                    let (id, rest) = line.split_once(" = ")?;
+                    id.starts_with('!').then_some((id, rest))
+                })
+                .collect();
+            let field = |node: &str, name: &str| -> Option<&str> {
+                let rest = metadata.get(node)?.split(&format!("{name}: ")).nth(1)?;
+                rest.split([',', ')']).next()
+            };
+            body.iter()
+                .map(|line| {
+                    let location = line.split("!dbg ").nth(1)?.split([',', ' ']).next()?;
+                    let scope = field(location, "scope")?;
+                    let file = field(scope, "file")?;
+                    let node = metadata.get(file)?;
+                    Some(
+                        node.split('"')
+                            .skip(1)
+                            .step_by(2)
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    )
+                })
+                .collect()
+        }
+    }
+}
+
+/// Whether `file` is part of `hashbrown`, whose SSE2 group probing isn't the
+/// loop being vectorized.
+pub fn is_from_hashbrown(file: Option<&str>) -> bool {
+    file.is_some_and(|file| file.contains("hashbrown"))
+}
@@ -0,0 +1,149 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `codegen` command itself. See the library for what it does.
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command};
+
+use codegen::{
+    asm_body, in_loops, is_bounds_check, is_from_hashbrown, is_vector, llvm_ir_body, source_files,
+    Emit,
+};
+
+const USAGE: &str = "usage: codegen [--llvm-ir] [--package <crate>] <function>";
+
+struct Args {
+    emit: Emit,
+    package: String,
+    function: String,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut emit = Emit::Asm;
+    let mut package = String::from("pets");
+    let mut function = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--llvm-ir" => emit = Emit::LlvmIr,
+            "--package" | "-p" => {
+                package = args.next().ok_or("--package needs a crate name")?;
+            }
+            "--help" | "-h" => return Err(USAGE.into()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {arg}\n{USAGE}")),
+            _ if function.is_none() => function = Some(arg),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    Ok(Args {
+        emit,
+        package,
+        function: function.ok_or(USAGE)?,
+    })
+}
+
+/// Builds `package` in release mode and returns the path of the assembly or
+/// LLVM IR file which rustc wrote for it.
+fn compile(package: &str, emit: Emit) -> Result<PathBuf, String> {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    // Use our own target directory, so the extra rustc flags don't cause
+    // everything else to be rebuilt.
+    let target_dir = workspace.join("target/codegen");
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let status = Command::new(cargo)
+        .current_dir(&workspace)
+        .args(["rustc", "--release", "--lib", "--package", package])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args(["--", emit.rustc_arg(), "-C", "codegen-units=1"])
+        .args(["-C", "debuginfo=line-tables-only"])
+        .status()
+        .map_err(|e| format!("unable to run cargo: {e}"))?;
+    if !status.success() {
+        return Err(format!("cargo failed to build {package}"));
+    }
+    // rustc names the file after the crate plus a hash. There may be stale
+    // ones from previous builds, so take the newest.
+    let deps = target_dir.join("release/deps");
+    let prefix = format!("{}-", package.replace('-', "_"));
+    fs::read_dir(&deps)
+        .map_err(|e| format!("unable to read {}: {e}", deps.display()))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension() == Some(OsStr::new(emit.extension()))
+                && path
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|name| name.starts_with(&prefix))
+        })
+        .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .ok_or_else(|| format!("rustc didn't write a .{} file", emit.extension()))
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|message| {
+        eprintln!("{message}");
+        exit(2);
+    });
+    let path = compile(&args.package, args.emit).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        exit(1);
+    });
+    let output = fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("unable to read {}: {e}", path.display());
+        exit(1);
+    });
+    let body = match args.emit {
+        Emit::Asm => asm_body(&output, &args.function),
+        Emit::LlvmIr => llvm_ir_body(&output, &args.function),
+    };
+    let Some(body) = body else {
+        eprintln!(
+            "{} not found in {}. It may have been inlined away, or be generic.",
+            args.function,
+            path.display()
+        );
+        exit(1);
+    };
+
+    let in_loop = in_loops(&body, args.emit);
+    let files = source_files(&output, &body, args.emit);
+    let mut bounds_checks = 0;
+    let mut vector_instructions = 0;
+    for (i, line) in body.iter().enumerate() {
+        let flag = if is_bounds_check(line) {
+            bounds_checks += 1;
+            "BOUNDS"
+        } else if in_loop[i]
+            && is_vector(line, args.emit)
+            && !is_from_hashbrown(files[i].as_deref())
+        {
+            vector_instructions += 1;
+            "SIMD"
+        } else {
+            ""
+        };
+        println!("{flag:>6} | {line}");
+    }
+    println!();
+    println!(
+        "{}: {bounds_checks} bounds check(s), {vector_instructions} vector instruction(s) in loops",
+        args.function
+    );
+}
@@ -0,0 +1,316 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codegen::{
+    asm_body, in_loops, is_bounds_check, is_from_hashbrown, is_vector, jump_targets, llvm_ir_body,
+    source_files, symbol_is, Emit,
+};
+
+const SHOPPING_LIST_A: &str = "_ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE";
+
+/// A loop with a bounds check, and SSE2 from both the function itself and
+/// `hashbrown`, roughly as rustc writes it for x86-64.
+const X86: &str = r#"	.file	"pets.2f3c4d5e6f7a8b9c-cgu.0"
+	.file	1 "/src/pets" "src/shopping.rs"
+	.file	2 "/cargo/registry/src/hashbrown-0.15.2" "src/control/group/sse2.rs"
+	.section	.text._ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE,"ax",@progbits
+	.globl	_ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE
+	.p2align	4
+	.type	_ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE,@function
+_ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE:
+	.cfi_startproc
+	.loc	1 20 0
+	xorl	%eax, %eax
+.LBB0_1:
+	cmpq	%rsi, %rax
+	jae	.LBB0_3
+	.loc	2 110 9
+	pcmpeqb	%xmm1, %xmm0
+	.loc	1 21 13
+	paddd	%xmm2, %xmm3
+	incq	%rax
+	jmp	.LBB0_1
+.LBB0_3:
+	.loc	1 21 12
+	callq	*_ZN4core9panicking18panic_bounds_check17h0123456789abcdefE@GOTPCREL(%rip)
+.Lfunc_end0:
+	.size	_ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE, .Lfunc_end0-_ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE
+	.cfi_endproc
+"#;
+
+/// The same function in LLVM IR, with one line inlined from `hashbrown`.
+const LLVM_IR: &str = r#"; pets::shopping::make_shopping_list_a
+; Function Attrs: nonlazybind uwtable
+define void @_ZN4pets8shopping20make_shopping_list_a17h0123456789abcdefE(ptr %_0, ptr %pets.0, i64 %pets.1) unnamed_addr #0 !dbg !10 {
+start:
+  br label %bb1, !dbg !12
+
+bb1:
+  %n = phi i64 [ 0, %start ], [ %n.next, %bb1 ]
+  %eq = icmp eq <16 x i8> %group, %tag, !dbg !13
+  %n.next = add i64 %n, 1, !dbg !12
+  %done = icmp eq i64 %n.next, %pets.1, !dbg !12
+  br i1 %done, label %bb2, label %bb1, !dbg !12
+
+bb2:
+  ret void, !dbg !12
+}
+
+define internal void @_ZN4pets8shopping20make_shopping_list_b17h0123456789abcdefE() unnamed_addr #0 {
+start:
+  ret void
+}
+
+!10 = distinct !DISubprogram(name: "make_shopping_list_a", scope: !11, file: !11, line: 20, unit: !9)
+!11 = !DIFile(filename: "src/shopping.rs", directory: "/src/pets")
+!12 = !DILocation(line: 21, column: 13, scope: !10)
+!13 = !DILocation(line: 110, column: 9, scope: !14)
+!14 = distinct !DILexicalBlock(scope: !15, file: !16, line: 109)
+!15 = distinct !DISubprogram(name: "match_tag", scope: !16, file: !16, line: 108, unit: !9)
+!16 = !DIFile(filename: "src/control/group/sse2.rs", directory: "/cargo/registry/src/hashbrown-0.15.2")
+"#;
+
+#[test]
+fn symbol_is_matches_a_whole_segment() {
+    assert!(symbol_is(SHOPPING_LIST_A, "make_shopping_list_a"));
+    assert!(symbol_is(SHOPPING_LIST_A, "shopping"));
+    assert!(!symbol_is(SHOPPING_LIST_A, "make_shopping_list"));
+    assert!(!symbol_is(SHOPPING_LIST_A, "list_a"));
+}
+
+#[test]
+fn symbol_is_ignores_segments_preceded_by_a_digit() {
+    // `11a_long_name` contains `1a`, but that's the tail of a length.
+    assert!(!symbol_is("_ZN4pets11a_long_name17h0123456789abcdefE", "a"));
+    assert!(symbol_is("_ZN4pets1a17h0123456789abcdefE", "a"));
+}
+
+#[test]
+fn asm_body_runs_from_label_to_function_end() {
+    let body = asm_body(X86, "make_shopping_list_a").unwrap();
+    assert_eq!(body.first(), Some(&&*format!("{SHOPPING_LIST_A}:")));
+    assert!(body.last().unwrap().contains("panic_bounds_check"));
+    assert_eq!(body.iter().filter(|line| is_bounds_check(line)).count(), 1);
+    // The `.globl` and `.type` lines mention the symbol too, but aren't labels.
+    assert_eq!(body.len(), 16);
+}
+
+#[test]
+fn asm_body_of_missing_function() {
+    assert_eq!(asm_body(X86, "make_shopping_list_b"), None);
+    assert_eq!(asm_body(X86, "make_shopping_list"), None);
+}
+
+#[test]
+fn llvm_ir_body_runs_from_define_to_closing_brace() {
+    let body = llvm_ir_body(LLVM_IR, "make_shopping_list_a").unwrap();
+    assert!(body[0].starts_with("define void @_ZN4pets8shopping20make_shopping_list_a"));
+    assert_eq!(body.last(), Some(&"}"));
+    assert_eq!(body.len(), 14);
+
+    let other = llvm_ir_body(LLVM_IR, "make_shopping_list_b").unwrap();
+    assert_eq!(other.len(), 4);
+    assert_eq!(llvm_ir_body(LLVM_IR, "make_shopping_list_c"), None);
+}
+
+#[test]
+fn x86_vector_instructions() {
+    for line in [
+        "\tpaddd\t%xmm1, %xmm0",
+        "\tvpaddq\t%ymm1, %ymm0, %ymm0",
+        "\tpcmpeqb\t%xmm1, %xmm0",
+        "\taddps\t%xmm1, %xmm0",
+        "\tvmulpd\t%ymm1, %ymm0, %ymm0",
+    ] {
+        assert!(is_vector(line, Emit::Asm), "{line}");
+    }
+    for line in [
+        "\taddq\t%rsi, %rax",
+        "\taddss\t%xmm1, %xmm0",
+        "\tmovdqa\t%xmm0, %xmm1",
+        "\tpxor\t%xmm0, %xmm0",
+        "\tpshufd\t$68, %xmm0, %xmm0",
+        "\t.p2align\t4",
+        ".LBB0_1:",
+    ] {
+        assert!(!is_vector(line, Emit::Asm), "{line}");
+    }
+}
+
+#[test]
+fn neon_vector_instructions() {
+    for line in [
+        "\tadd\tv0.4s, v0.4s, v1.4s",
+        "\tcmeq\tv1.16b, v1.16b, #0",
+        "\tfadd\tv0.2d, v0.2d, v2.2d",
+        "\tumaxv\th0, v0.8h",
+    ] {
+        assert!(is_vector(line, Emit::Asm), "{line}");
+    }
+    for line in [
+        "\tadd\tx8, x8, #1",
+        "\tfadd\td0, d0, d1",
+        "\tld1\t{ v0.16b }, [x0]",
+        "\tmov\tv1.16b, v0.16b",
+    ] {
+        assert!(!is_vector(line, Emit::Asm), "{line}");
+    }
+}
+
+#[test]
+fn llvm_ir_vector_instructions() {
+    assert!(is_vector("  %5 = add <4 x i32> %3, %4", Emit::LlvmIr));
+    assert!(is_vector(
+        "  %eq = icmp eq <16 x i8> %group, %tag",
+        Emit::LlvmIr
+    ));
+    assert!(!is_vector("  %n.next = add i64 %n, 1", Emit::LlvmIr));
+    assert!(!is_vector("  %a = load [4 x i32], ptr %p", Emit::LlvmIr));
+    assert!(!is_vector(
+        "  %s = shufflevector <4 x i32> %a, <4 x i32> %b, <4 x i32> zeroinitializer",
+        Emit::LlvmIr
+    ));
+    assert!(!is_vector("  store <4 x i32> %v, ptr %p", Emit::LlvmIr));
+}
+
+#[test]
+fn x86_jump_targets() {
+    assert_eq!(jump_targets("\tjae\t.LBB0_3", Emit::Asm), [".LBB0_3"]);
+    assert_eq!(jump_targets("\tjmp\t.LBB0_1", Emit::Asm), [".LBB0_1"]);
+    assert!(jump_targets("\tjmpq\t*%rax", Emit::Asm).is_empty());
+    assert!(jump_targets("\tcallq\tfoo", Emit::Asm).is_empty());
+}
+
+#[test]
+fn neon_jump_targets() {
+    assert_eq!(jump_targets("\tb.ne\t.LBB0_2", Emit::Asm), [".LBB0_2"]);
+    assert_eq!(jump_targets("\tb\t.LBB0_5", Emit::Asm), [".LBB0_5"]);
+    assert_eq!(jump_targets("\tcbnz\tx8, .LBB0_4", Emit::Asm), [".LBB0_4"]);
+    assert_eq!(
+        jump_targets("\ttbz\tw9, #0, .LBB0_7", Emit::Asm),
+        [".LBB0_7"]
+    );
+    assert!(jump_targets("\tbl\tfoo", Emit::Asm).is_empty());
+}
+
+#[test]
+fn llvm_ir_jump_targets() {
+    assert_eq!(
+        jump_targets(
+            "  br i1 %done, label %bb2, label %bb1, !dbg !12",
+            Emit::LlvmIr
+        ),
+        ["bb2", "bb1"]
+    );
+    assert_eq!(jump_targets("  br label %start", Emit::LlvmIr), ["start"]);
+    assert!(jump_targets("  ret void", Emit::LlvmIr).is_empty());
+}
+
+#[test]
+fn x86_loop_runs_from_label_to_backward_jump() {
+    let body = asm_body(X86, "make_shopping_list_a").unwrap();
+    let in_loop = in_loops(&body, Emit::Asm);
+    let looped: Vec<&str> = body
+        .iter()
+        .zip(&in_loop)
+        .filter(|(_, &in_loop)| in_loop)
+        .map(|(line, _)| line.trim())
+        .collect();
+    // The forward jump to `.LBB0_3` isn't a loop.
+    assert_eq!(looped.first(), Some(&".LBB0_1:"));
+    assert_eq!(looped.last(), Some(&"jmp\t.LBB0_1"));
+    assert_eq!(looped.len(), 9);
+}
+
+#[test]
+fn llvm_ir_loop_is_the_block_which_branches_to_itself() {
+    let body = llvm_ir_body(LLVM_IR, "make_shopping_list_a").unwrap();
+    let in_loop = in_loops(&body, Emit::LlvmIr);
+    let looped: Vec<&str> = body
+        .iter()
+        .zip(&in_loop)
+        .filter(|(_, &in_loop)| in_loop)
+        .map(|(line, _)| line.trim())
+        .collect();
+    assert_eq!(looped.first(), Some(&"bb1:"));
+    assert!(looped.last().unwrap().starts_with("br i1 %done"));
+    assert_eq!(looped.len(), 6);
+}
+
+#[test]
+fn x86_source_files_follow_loc_directives() {
+    let body = asm_body(X86, "make_shopping_list_a").unwrap();
+    let files = source_files(X86, &body, Emit::Asm);
+    let file_of = |instruction: &str| {
+        let i = body.iter().position(|line| line.contains(instruction));
+        files[i.unwrap()].as_deref()
+    };
+    assert_eq!(files[0], None);
+    assert_eq!(file_of("xorl"), Some("/src/pets/src/shopping.rs"));
+    assert_eq!(
+        file_of("pcmpeqb"),
+        Some("/cargo/registry/src/hashbrown-0.15.2/src/control/group/sse2.rs")
+    );
+    assert_eq!(file_of("paddd"), Some("/src/pets/src/shopping.rs"));
+}
+
+#[test]
+fn llvm_ir_source_files_follow_debug_locations() {
+    let body = llvm_ir_body(LLVM_IR, "make_shopping_list_a").unwrap();
+    let files = source_files(LLVM_IR, &body, Emit::LlvmIr);
+    let file_of = |instruction: &str| {
+        let i = body.iter().position(|line| line.contains(instruction));
+        files[i.unwrap()].as_deref()
+    };
+    assert!(file_of("%n.next = add").is_some_and(|file| file.contains("src/shopping.rs")));
+    assert!(file_of("icmp eq <16 x i8>").is_some_and(|file| file.contains("hashbrown")));
+    assert_eq!(file_of("phi i64"), None);
+}
+
+/// What `codegen` counts: vector instructions in loops, other than those
+/// inlined from `hashbrown`.
+fn simd_count(output: &str, body: &[&str], emit: Emit) -> usize {
+    let in_loop = in_loops(body, emit);
+    let files = source_files(output, body, emit);
+    (0..body.len())
+        .filter(|&i| {
+            in_loop[i] && is_vector(body[i], emit) && !is_from_hashbrown(files[i].as_deref())
+        })
+        .count()
+}
+
+#[test]
+fn hashbrown_probing_isnt_vectorization() {
+    let body = asm_body(X86, "make_shopping_list_a").unwrap();
+    assert_eq!(
+        body.iter()
+            .filter(|line| is_vector(line, Emit::Asm))
+            .count(),
+        2
+    );
+    // Only the `paddd`.
+    assert_eq!(simd_count(X86, &body, Emit::Asm), 1);
+
+    let body = llvm_ir_body(LLVM_IR, "make_shopping_list_a").unwrap();
+    assert_eq!(simd_count(LLVM_IR, &body, Emit::LlvmIr), 0);
+}
+
+#[test]
+fn is_from_hashbrown_needs_a_file() {
+    assert!(is_from_hashbrown(Some(
+        "/cargo/registry/src/hashbrown-0.15.2/src/raw/mod.rs"
+    )));
+    assert!(!is_from_hashbrown(Some("/src/pets/src/shopping.rs")));
+    assert!(!is_from_hashbrown(None));
+}
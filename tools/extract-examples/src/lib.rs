@@ -0,0 +1,547 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pulls every Rust code block out of the book into its own example target
+//! in a generated Cargo package, so that the snippets get the same checks as
+//! real code.
+//!
+//! ```text
+//! cargo run -p extract-examples                         # just write target/book-examples
+//! cargo run -p extract-examples -- clippy --examples
+//! cargo run -p extract-examples -- test --examples
+//! cargo run -p extract-examples -- miri test --examples
+//! ```
+//!
+//! Any other arguments are run as a `cargo` command in the generated package,
+//! and file names and line numbers in its output are mapped back to the
+//! markdown. Each example is named after where it came from: `code_13` is the
+//! code block starting at line 13 of `src/code.md`.
+//!
+//! Hidden lines, `{{#include_hidden}}` and `prelude=` are expanded just as
+//! they are for `mdbook test`. As with rustdoc, a snippet without a `fn main`
+//! is wrapped in one. Each example also gets a `#[test]` which calls `main`,
+//! so `cargo test` runs the snippets, honouring `should_panic` and `no_run`.
+//! `ignore` and `compile_fail` blocks aren't extracted.
+//!
+//! A commented-out line whose trailing comment ends in an error code, like
+//! `// b(&vec![format!("hello")]); // doesn't work (E0308)`, is a claim that
+//! the line doesn't compile. For `cargo test`, each such line is uncommented
+//! in a copy of its code block, in a second package under `compile-fail/`,
+//! and the copy has to fail with that error code.
+//!
+//! Warnings, including the `rust_2018_idioms` lints, are errors, except for
+//! clippy's: some snippets are unidiomatic on purpose.
+//!
+//! The generated package depends on the crates `book-deps` provides, so code
+//! blocks using `prelude=crates` work here too.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use mdbook_include_hidden::{parse_directive, parse_fence, take_preludes, Preludes};
+
+/// For each example, its chapter and where each of its lines came from.
+pub type LineMaps = HashMap<String, (String, Vec<Origin>)>;
+
+/// Where a line of an extracted example came from.
+#[derive(Clone, Copy)]
+pub struct Origin {
+    /// 1-based line in the chapter.
+    pub line: usize,
+    /// How far the code block was indented, so columns can be fixed up.
+    pub indent: usize,
+}
+
+/// A code block, ready to be written out as an example.
+#[derive(Clone)]
+pub struct Snippet {
+    pub name: String,
+    /// The chapter, relative to the book root, e.g. `src/code.md`.
+    pub chapter: String,
+    pub fence: Origin,
+    pub edition: Option<String>,
+    pub should_panic: bool,
+    pub no_run: bool,
+    pub lines: Vec<(String, Origin)>,
+}
+
+/// The parts of a code block's info string we care about, or `None` if it
+/// isn't a Rust block which should be compiled.
+pub struct Attributes {
+    pub edition: Option<String>,
+    pub should_panic: bool,
+    pub no_run: bool,
+}
+
+pub fn parse_attributes(info: &str) -> Option<Attributes> {
+    let mut attributes = Attributes {
+        edition: None,
+        should_panic: false,
+        no_run: false,
+    };
+    for token in info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+    {
+        match token {
+            "rust" | "allow_fail" | "test_harness" => {}
+            "should_panic" => attributes.should_panic = true,
+            "no_run" => attributes.no_run = true,
+            "ignore" | "compile_fail" => return None,
+            _ if token.starts_with("edition") => {
+                attributes.edition = Some(token["edition".len()..].to_owned());
+            }
+            // Another language entirely, e.g. `mermaid` or `cpp`.
+            _ => return None,
+        }
+    }
+    Some(attributes)
+}
+
+/// Undoes rustdoc's hidden line markers.
+pub fn unhide(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    if trimmed == "#" {
+        ""
+    } else if let Some(rest) = trimmed.strip_prefix("# ") {
+        rest
+    } else if trimmed.starts_with("##") {
+        &trimmed[1..]
+    } else {
+        line
+    }
+}
+
+fn read_file_lines(path: &Path) -> Result<Vec<String>, String> {
+    fs::read_to_string(path)
+        .map(|text| text.lines().map(str::to_owned).collect())
+        .map_err(|e| format!("unable to read {}: {e}", path.display()))
+}
+
+/// Finds the Rust code blocks in one chapter.
+pub fn extract_chapter(
+    book_root: &Path,
+    path: &Path,
+    preludes: &Preludes,
+) -> Result<Vec<Snippet>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("unable to read {}: {e}", path.display()))?;
+    let dir = path.parent().unwrap_or(book_root);
+    let chapter = path
+        .strip_prefix(book_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .into_owned();
+    let stem = chapter
+        .trim_end_matches(".md")
+        .split_once('/')
+        .map_or(chapter.as_str(), |(_, rest)| rest)
+        .replace(['/', '-', '.'], "_");
+
+    let mut snippets = Vec::new();
+    let mut lines = content.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let trimmed = line.trim_start();
+        let Some((fence, info)) = parse_fence(trimmed) else {
+            continue;
+        };
+        let indent = line.len() - trimmed.len();
+        let fence_origin = Origin {
+            line: index + 1,
+            indent,
+        };
+        let (info, prelude_names) = take_preludes(info);
+        let attributes = parse_attributes(&info);
+
+        let mut code = Vec::new();
+        if attributes.is_some() {
+            for name in prelude_names {
+                let files = preludes
+                    .get(name)
+                    .ok_or_else(|| format!("{chapter}: no prelude called {name:?}"))?;
+                for file in files {
+                    code.extend(
+                        read_file_lines(file)?
+                            .into_iter()
+                            .map(|line| (line, fence_origin)),
+                    );
+                }
+            }
+        }
+        for (index, line) in lines.by_ref() {
+            let origin = Origin {
+                line: index + 1,
+                indent,
+            };
+            let body = line.get(indent..).unwrap_or(line.trim_start());
+            if body.trim_end() == fence {
+                break;
+            }
+            if attributes.is_none() {
+                continue;
+            }
+            match parse_directive(body.trim_start()) {
+                Some(include) => code.extend(
+                    read_file_lines(&dir.join(include))?
+                        .into_iter()
+                        .map(|line| (line, origin)),
+                ),
+                None => code.push((unhide(body).to_owned(), origin)),
+            }
+        }
+
+        if let Some(attributes) = attributes {
+            snippets.push(Snippet {
+                name: format!("{stem}_{}", fence_origin.line),
+                chapter: chapter.clone(),
+                fence: fence_origin,
+                edition: attributes.edition,
+                should_panic: attributes.should_panic,
+                no_run: attributes.no_run,
+                lines: code,
+            });
+        }
+    }
+    Ok(snippets)
+}
+
+/// The chapters listed in `SUMMARY.md`, in order.
+pub fn chapters(src_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let summary = fs::read_to_string(src_dir.join("SUMMARY.md"))
+        .map_err(|e| format!("unable to read SUMMARY.md: {e}"))?;
+    Ok(summary
+        .split("](")
+        .skip(1)
+        .filter_map(|link| link.split(')').next())
+        .filter(|link| link.ends_with(".md"))
+        .map(|link| src_dir.join(link.trim_start_matches("./")))
+        .collect())
+}
+
+/// A commented-out line which the book says doesn't compile.
+pub struct FailingLine<'a> {
+    pub indent: &'a str,
+    pub code: &'a str,
+    pub error_code: &'a str,
+}
+
+/// Parses `// code; // comment (E0123)`.
+pub fn parse_failing_line(line: &str) -> Option<FailingLine<'_>> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let (code, comment) = trimmed.strip_prefix("//")?.rsplit_once("//")?;
+    let error_code = comment.trim_end().strip_suffix(')')?.rsplit_once('(')?.1;
+    let is_error_code = error_code.len() == 5
+        && error_code.starts_with('E')
+        && error_code[1..].bytes().all(|b| b.is_ascii_digit());
+    let code = code.trim();
+    (is_error_code && !code.is_empty()).then_some(FailingLine {
+        indent,
+        code,
+        error_code,
+    })
+}
+
+/// A copy of a snippet with one [failing line](parse_failing_line)
+/// uncommented, which should fail to compile.
+pub struct CompileFail {
+    pub snippet: Snippet,
+    /// Where the failing line is.
+    pub line: Origin,
+    pub error_code: String,
+}
+
+/// A [`CompileFail`] case for each failing line in `snippet`. Each is named
+/// after the line, e.g. `signatures_58_fails_67`.
+pub fn compile_fail_cases(snippet: &Snippet) -> Vec<CompileFail> {
+    let mut cases = Vec::new();
+    for (index, (line, origin)) in snippet.lines.iter().enumerate() {
+        let Some(failing) = parse_failing_line(line) else {
+            continue;
+        };
+        let mut case = snippet.clone();
+        case.name = format!("{}_fails_{}", snippet.name, origin.line);
+        case.lines[index].0 = format!("{}{}", failing.indent, failing.code);
+        cases.push(CompileFail {
+            snippet: case,
+            line: *origin,
+            error_code: failing.error_code.to_owned(),
+        });
+    }
+    cases
+}
+
+/// Writes `snippet` as Rust source, returning the origin of each line.
+pub fn render(snippet: &Snippet) -> (String, Vec<Origin>) {
+    let mut source = String::new();
+    let mut origins = Vec::new();
+    let mut push = |source: &mut String, line: &str, origin: Origin| {
+        source.push_str(line);
+        source.push('\n');
+        origins.push(origin);
+    };
+    let header = format!(
+        "// Extracted from {} line {} by extract-examples. Edit the book, not this.",
+        snippet.chapter, snippet.fence.line
+    );
+    push(&mut source, &header, snippet.fence);
+    push(
+        &mut source,
+        "#![cfg_attr(not(clippy), deny(warnings, rust_2018_idioms))]",
+        snippet.fence,
+    );
+    push(&mut source, "#![allow(unused)]", snippet.fence);
+    let has_main = snippet
+        .lines
+        .iter()
+        .any(|(line, _)| line.contains("fn main("));
+    if !has_main {
+        push(&mut source, "fn main() {", snippet.fence);
+    }
+    for (line, origin) in &snippet.lines {
+        push(&mut source, line, *origin);
+    }
+    if !has_main {
+        push(&mut source, "}", snippet.fence);
+    }
+    if !snippet.no_run {
+        push(&mut source, "", snippet.fence);
+        push(&mut source, "#[test]", snippet.fence);
+        if snippet.should_panic {
+            push(&mut source, "#[should_panic]", snippet.fence);
+        }
+        push(&mut source, "fn snippet() {", snippet.fence);
+        push(&mut source, "    main();", snippet.fence);
+        push(&mut source, "}", snippet.fence);
+    }
+    (source, origins)
+}
+
+/// Writes the generated package, returning the line origins of each example.
+pub fn write_package(
+    out: &Path,
+    snippets: &[Snippet],
+    edition: &str,
+    dependencies: &str,
+) -> Result<LineMaps, String> {
+    let io_error = |e: std::io::Error| format!("unable to write {}: {e}", out.display());
+    let examples = out.join("examples");
+    if examples.exists() {
+        fs::remove_dir_all(&examples).map_err(io_error)?;
+    }
+    fs::create_dir_all(&examples).map_err(io_error)?;
+    fs::create_dir_all(out.join("src")).map_err(io_error)?;
+    fs::write(
+        out.join("src/lib.rs"),
+        "// Everything interesting is in examples/.\n",
+    )
+    .map_err(io_error)?;
+
+    let mut manifest = format!(
+        "# Generated by extract-examples from the book. Edit the book, not this.\n\
+         [package]\n\
+         name = \"book-examples\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"{edition}\"\n\
+         publish = false\n\
+         \n\
+         # Not part of the book's own workspace.\n\
+         [workspace]\n\
+         \n\
+         {dependencies}"
+    );
+    let mut maps = HashMap::new();
+    for snippet in snippets {
+        let (source, origins) = render(snippet);
+        fs::write(examples.join(format!("{}.rs", snippet.name)), source).map_err(io_error)?;
+        write!(
+            manifest,
+            "\n[[example]]\nname = \"{}\"\npath = \"examples/{}.rs\"\ntest = true\n",
+            snippet.name, snippet.name,
+        )
+        .unwrap();
+        if let Some(edition) = &snippet.edition {
+            writeln!(manifest, "edition = \"{edition}\"").unwrap();
+        }
+        maps.insert(snippet.name.clone(), (snippet.chapter.clone(), origins));
+    }
+    fs::write(out.join("Cargo.toml"), manifest).map_err(io_error)?;
+    // Start from the workspace's lock file, so that the examples get the same
+    // versions of `book-deps`' crates and nothing new needs fetching.
+    let lock_file = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../Cargo.lock");
+    if lock_file.exists() {
+        fs::copy(&lock_file, out.join("Cargo.lock")).map_err(io_error)?;
+    }
+    Ok(maps)
+}
+
+/// Rewrites every `examples/<name>.rs:<line>:<column>` in `text` to point at
+/// the markdown instead, along with the line numbers in the gutter of a
+/// rendered diagnostic.
+pub fn remap(text: &str, maps: &LineMaps) -> String {
+    remap_paths(&remap_gutter(text, maps), maps)
+}
+
+/// The lines of `example`, if it's one of ours.
+fn example_origins<'m>(path: &str, maps: &'m LineMaps) -> Option<&'m [Origin]> {
+    let (name, _) = path.strip_prefix("examples/")?.split_once(".rs:")?;
+    maps.get(name).map(|(_, origins)| origins.as_slice())
+}
+
+/// The byte offset and value of each character of `line` which isn't part of
+/// an ANSI escape sequence, as found in `json-diagnostic-rendered-ansi`.
+fn visible_chars(line: &str) -> Vec<(usize, char)> {
+    let mut visible = Vec::new();
+    let mut chars = line.char_indices();
+    while let Some((offset, c)) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequences end with a letter.
+            chars
+                .by_ref()
+                .skip(1)
+                .find(|(_, c)| c.is_ascii_alphabetic());
+        } else {
+            visible.push((offset, c));
+        }
+    }
+    visible
+}
+
+/// Renumbers the gutter of a rendered diagnostic, the `75 |` before each
+/// quoted line, to match the markdown. The gutter is widened if need be.
+fn remap_gutter(text: &str, maps: &LineMaps) -> String {
+    let lines: Vec<_> = text
+        .split_inclusive('\n')
+        .map(|line| (line, visible_chars(line)))
+        .collect();
+    // rustc indents `--> file:line:column` by the width of the gutter.
+    let Some(width) = lines.iter().find_map(|(_, visible)| {
+        let spaces = visible.iter().take_while(|(_, c)| *c == ' ').count();
+        let arrow: String = visible
+            .iter()
+            .skip(spaces)
+            .take(4)
+            .map(|(_, c)| c)
+            .collect();
+        (spaces > 0 && arrow == "--> ").then_some(spaces)
+    }) else {
+        return text.to_owned();
+    };
+
+    // For each line, `None` if it isn't in the gutter, such as the message
+    // itself. Otherwise, the new line number, or `None` if there isn't one.
+    let mut gutter = Vec::with_capacity(lines.len());
+    let mut origins = None;
+    for (_, visible) in &lines {
+        let number: String = visible.iter().take(width).map(|(_, c)| c).collect();
+        let rest: String = visible.iter().skip(width).map(|(_, c)| c).collect();
+        if rest.is_empty() || !number.chars().all(|c| c == ' ' || c.is_ascii_digit()) {
+            gutter.push(None);
+            continue;
+        }
+        if let Some(path) = rest.strip_prefix("--> ").or(rest.strip_prefix("::: ")) {
+            origins = example_origins(path, maps);
+        }
+        let number = number.trim_start();
+        if number.is_empty() {
+            gutter.push(Some(None));
+            continue;
+        }
+        // The number of a quoted line, or of a line in a suggested change.
+        let quoted = [" |", " +", " -", " ~"].iter().any(|s| rest.starts_with(s));
+        match number.parse::<usize>() {
+            Ok(line) if quoted => {
+                let mapped = origins
+                    .and_then(|origins| origins.get(line.checked_sub(1)?))
+                    .map_or(line, |origin| origin.line);
+                gutter.push(Some(Some(mapped)));
+            }
+            _ => gutter.push(None),
+        }
+    }
+
+    let new_width = gutter
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|line| line.to_string().len())
+        .fold(width, usize::max);
+    let mut output = String::with_capacity(text.len());
+    for ((line, visible), gutter) in lines.iter().zip(gutter) {
+        let Some(number) = gutter else {
+            output.push_str(line);
+            continue;
+        };
+        let start = visible[0].0;
+        let (end, last) = visible[width - 1];
+        output.push_str(&line[..start]);
+        match number {
+            Some(number) => output.push_str(&format!("{number:>new_width$}")),
+            None => output.push_str(&" ".repeat(new_width)),
+        }
+        output.push_str(&line[end + last.len_utf8()..]);
+    }
+    output
+}
+
+fn remap_paths(text: &str, maps: &LineMaps) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("examples/") {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let mapped = (|| {
+            let after = &rest["examples/".len()..];
+            let (name, after) = after.split_once(".rs:")?;
+            let (chapter, origins) = maps.get(name)?;
+            let line_len = after
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(after.len());
+            let line: usize = after[..line_len].parse().ok()?;
+            let origin = origins.get(line.checked_sub(1)?)?;
+            let after = &after[line_len..];
+            let column = after.strip_prefix(':').and_then(|after| {
+                let len = after
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(after.len());
+                Some((after[..len].parse::<usize>().ok()?, len + 1))
+            });
+            let (text, consumed) = match column {
+                Some((column, len)) => (
+                    format!("{chapter}:{}:{}", origin.line, column + origin.indent),
+                    line_len + len,
+                ),
+                None => (format!("{chapter}:{}", origin.line), line_len),
+            };
+            Some((
+                text,
+                "examples/".len() + name.len() + ".rs:".len() + consumed,
+            ))
+        })();
+        match mapped {
+            Some((text, consumed)) => {
+                output.push_str(&text);
+                rest = &rest[consumed..];
+            }
+            None => {
+                output.push_str("examples/");
+                rest = &rest["examples/".len()..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
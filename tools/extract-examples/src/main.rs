@@ -0,0 +1,241 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `extract-examples` command itself. See the library for what it does.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command, Stdio};
+
+use extract_examples::{
+    chapters, compile_fail_cases, extract_chapter, remap, write_package, CompileFail, LineMaps,
+};
+use mdbook_include_hidden::read_preludes;
+use serde_json::Value;
+
+const USAGE: &str = "usage: extract-examples [--book <dir>] [--out <dir>] [<cargo args>...]";
+
+/// Runs `cargo <args>` in the generated package, with diagnostics mapped back
+/// to the book. Returns cargo's exit code.
+fn run_cargo(out: &Path, args: &[String], maps: &LineMaps) -> Result<i32, String> {
+    // --message-format has to come before any `--` meant for the tests.
+    let split = args
+        .iter()
+        .position(|arg| arg == "--")
+        .unwrap_or(args.len());
+    let mut child = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .current_dir(out)
+        .args(&args[..split])
+        .arg(if io::stderr().is_terminal() {
+            "--message-format=json-diagnostic-rendered-ansi"
+        } else {
+            "--message-format=json"
+        })
+        .args(&args[split..])
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("unable to run cargo: {e}"))?;
+    for line in BufReader::new(child.stdout.take().unwrap()).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        match serde_json::from_str::<Value>(&line) {
+            Ok(message) if message.get("reason").is_some() => {
+                if message["reason"] == "compiler-message" {
+                    if let Some(rendered) = message["message"]["rendered"].as_str() {
+                        eprint!("{}", remap(rendered, maps));
+                    }
+                }
+            }
+            _ => println!("{}", remap(&line, maps)),
+        }
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Builds the package of `cases` in `dir`, and checks that each fails to
+/// compile with its error code. Returns whether they all did.
+fn check_compile_fail(
+    dir: &Path,
+    target_dir: &Path,
+    cases: &[CompileFail],
+    maps: &LineMaps,
+) -> Result<bool, String> {
+    let output = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .current_dir(dir)
+        .args(["check", "--examples", "--keep-going", "--target-dir"])
+        .arg(target_dir)
+        .arg(if io::stderr().is_terminal() {
+            "--message-format=json-diagnostic-rendered-ansi"
+        } else {
+            "--message-format=json"
+        })
+        .output()
+        .map_err(|e| format!("unable to run cargo: {e}"))?;
+    // The error code, if any, and rendered text of each example's errors.
+    let mut errors: HashMap<String, Vec<(Option<String>, String)>> = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if message["reason"] != "compiler-message" || message["message"]["level"] != "error" {
+            continue;
+        }
+        let Some(name) = message["target"]["name"].as_str() else {
+            continue;
+        };
+        let code = message["message"]["code"]["code"]
+            .as_str()
+            .map(str::to_owned);
+        let rendered = message["message"]["rendered"].as_str().unwrap_or_default();
+        errors
+            .entry(name.to_owned())
+            .or_default()
+            .push((code, remap(rendered, maps)));
+    }
+    if errors.is_empty() && !output.status.success() {
+        // Not the examples' fault, e.g. a dependency which didn't build.
+        return Err(format!(
+            "unable to build {}:\n{}",
+            dir.display(),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let mut passed = 0;
+    for case in cases {
+        let location = format!("{}:{}", case.snippet.chapter, case.line.line);
+        let found = errors
+            .get(&case.snippet.name)
+            .map_or(&[][..], Vec::as_slice);
+        if found
+            .iter()
+            .any(|(code, _)| code.as_deref() == Some(&case.error_code))
+        {
+            passed += 1;
+        } else if found.is_empty() {
+            eprintln!(
+                "{location}: expected error {}, but it compiles",
+                case.error_code
+            );
+        } else {
+            for (_, rendered) in found {
+                eprint!("{rendered}");
+            }
+            eprintln!("{location}: expected error {}, not these", case.error_code);
+        }
+    }
+    eprintln!(
+        "{passed} of {} lines which shouldn't compile failed as expected",
+        cases.len()
+    );
+    Ok(passed == cases.len())
+}
+
+/// The `[dependencies]` of `book-deps`, which are the crates code blocks may
+/// use, as TOML for the generated manifest.
+fn book_dependencies() -> Result<String, String> {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("../book-deps/Cargo.toml");
+    let mut config: toml::Table = toml::from_str(
+        &fs::read_to_string(&manifest)
+            .map_err(|e| format!("unable to read {}: {e}", manifest.display()))?,
+    )
+    .map_err(|e| format!("bad {}: {e}", manifest.display()))?;
+    let mut dependencies = toml::Table::new();
+    if let Some(toml::Value::Table(mut table)) = config.remove("dependencies") {
+        // `book-deps` itself reads cargo's output with it; the book doesn't.
+        table.remove("serde_json");
+        dependencies.insert("dependencies".to_owned(), table.into());
+    }
+    toml::to_string(&dependencies).map_err(|e| e.to_string())
+}
+
+fn run() -> Result<i32, String> {
+    let mut book_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let mut out = None;
+    let mut cargo_args = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--book" => book_root = args.next().ok_or(USAGE)?.into(),
+            "--out" => out = Some(PathBuf::from(args.next().ok_or(USAGE)?)),
+            "--help" | "-h" => return Err(USAGE.into()),
+            "--" => cargo_args.extend(args.by_ref()),
+            _ => {
+                cargo_args.push(arg);
+                cargo_args.extend(args.by_ref());
+            }
+        }
+    }
+    let book_root = book_root
+        .canonicalize()
+        .map_err(|e| format!("unable to find {}: {e}", book_root.display()))?;
+    let out = out.unwrap_or_else(|| book_root.join("target/book-examples"));
+
+    let book_toml = book_root.join("book.toml");
+    let config: Value = toml::from_str(
+        &fs::read_to_string(&book_toml)
+            .map_err(|e| format!("unable to read {}: {e}", book_toml.display()))?,
+    )
+    .map_err(|e| format!("bad {}: {e}", book_toml.display()))?;
+    let src_dir = book_root.join(config["book"]["src"].as_str().unwrap_or("src"));
+    // mdbook's default, so that the examples behave as they do in `mdbook test`.
+    let edition = config["rust"]["edition"].as_str().unwrap_or("2015");
+    let preludes = read_preludes(&config, &src_dir)?;
+
+    let mut snippets = Vec::new();
+    for chapter in chapters(&src_dir)? {
+        snippets.extend(extract_chapter(&book_root, &chapter, &preludes)?);
+    }
+    let cases: Vec<_> = snippets.iter().flat_map(compile_fail_cases).collect();
+    let dependencies = book_dependencies()?;
+    let mut maps = write_package(&out, &snippets, edition, &dependencies)?;
+    let compile_fail_dir = out.join("compile-fail");
+    let case_snippets: Vec<_> = cases.iter().map(|case| case.snippet.clone()).collect();
+    maps.extend(write_package(
+        &compile_fail_dir,
+        &case_snippets,
+        edition,
+        &dependencies,
+    )?);
+    eprintln!(
+        "Wrote {} examples and {} compile_fail cases to {}",
+        snippets.len(),
+        cases.len(),
+        out.display()
+    );
+
+    if cargo_args.is_empty() {
+        return Ok(0);
+    }
+    let code = run_cargo(&out, &cargo_args, &maps)?;
+    if cargo_args[0] == "test"
+        && !check_compile_fail(&compile_fail_dir, &out.join("target"), &cases, &maps)?
+    {
+        return Ok(code.max(1));
+    }
+    Ok(code)
+}
+
+fn main() {
+    match run() {
+        Ok(code) => exit(code),
+        Err(message) => {
+            eprintln!("extract-examples: {message}");
+            exit(2);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use extract_examples::{
+    compile_fail_cases, parse_attributes, parse_failing_line, remap, render, unhide, LineMaps,
+    Origin, Snippet,
+};
+
+fn origin(line: usize) -> Origin {
+    Origin { line, indent: 2 }
+}
+
+fn snippet(lines: &[&str]) -> Snippet {
+    Snippet {
+        name: "code_10".to_owned(),
+        chapter: "src/code.md".to_owned(),
+        fence: origin(10),
+        edition: None,
+        should_panic: false,
+        no_run: false,
+        lines: lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| (line.to_string(), origin(11 + i)))
+            .collect(),
+    }
+}
+
+/// `code_10` is lines 11 onwards of `src/code.md`, after a two line header.
+fn maps() -> LineMaps {
+    let origins = [10, 10, 11, 12, 13].map(origin).to_vec();
+    HashMap::from([("code_10".to_owned(), ("src/code.md".to_owned(), origins))])
+}
+
+#[test]
+fn plain_rust_blocks_are_extracted() {
+    for info in ["", "rust", "rust,allow_fail", "rust test_harness"] {
+        let attributes = parse_attributes(info).unwrap();
+        assert_eq!(attributes.edition, None);
+        assert!(!attributes.should_panic);
+        assert!(!attributes.no_run);
+    }
+}
+
+#[test]
+fn attributes_are_parsed() {
+    let attributes = parse_attributes("rust,should_panic,no_run,edition2018").unwrap();
+    assert_eq!(attributes.edition.as_deref(), Some("2018"));
+    assert!(attributes.should_panic);
+    assert!(attributes.no_run);
+}
+
+#[test]
+fn other_blocks_are_skipped() {
+    for info in ["rust,ignore", "rust,compile_fail", "mermaid", "cpp", "text"] {
+        assert!(parse_attributes(info).is_none(), "{info}");
+    }
+}
+
+#[test]
+fn hidden_lines_are_shown() {
+    assert_eq!(unhide("# let x = 1;"), "let x = 1;");
+    assert_eq!(unhide("    # let x = 1;"), "let x = 1;");
+    assert_eq!(unhide("#"), "");
+    assert_eq!(unhide("##[derive(Debug)]"), "#[derive(Debug)]");
+    assert_eq!(unhide("#[derive(Debug)]"), "#[derive(Debug)]");
+    assert_eq!(unhide("    let x = 1;"), "    let x = 1;");
+}
+
+#[test]
+fn snippet_is_wrapped_in_main_and_tested() {
+    let (source, origins) = render(&snippet(&["let x = 1;", "assert_eq!(x, 1);"]));
+    let lines: Vec<_> = source.lines().collect();
+    assert_eq!(lines.len(), origins.len());
+    assert_eq!(
+        lines[3..],
+        [
+            "fn main() {",
+            "let x = 1;",
+            "assert_eq!(x, 1);",
+            "}",
+            "",
+            "#[test]",
+            "fn snippet() {",
+            "    main();",
+            "}",
+        ]
+    );
+    assert_eq!(origins[4].line, 11);
+    assert_eq!(origins[5].line, 12);
+    // Generated lines point at the fence.
+    assert!(lines[0].contains("src/code.md line 10"));
+    assert_eq!(origins[0].line, 10);
+    assert_eq!(origins[3].line, 10);
+}
+
+#[test]
+fn existing_main_is_kept() {
+    let (source, _) = render(&snippet(&["fn main() {", "}"]));
+    assert_eq!(source.matches("fn main()").count(), 1);
+}
+
+#[test]
+fn attributes_shape_the_test() {
+    let mut panics = snippet(&["panic!();"]);
+    panics.should_panic = true;
+    assert!(render(&panics).0.contains("#[should_panic]\nfn snippet()"));
+
+    let mut no_run = snippet(&["loop {}"]);
+    no_run.no_run = true;
+    assert!(!render(&no_run).0.contains("#[test]"));
+}
+
+#[test]
+fn failing_lines_are_parsed() {
+    let failing = parse_failing_line("    // a(&[\"hi\"]); // doesn't work (E0308)").unwrap();
+    assert_eq!(failing.indent, "    ");
+    assert_eq!(failing.code, "a(&[\"hi\"]);");
+    assert_eq!(failing.error_code, "E0308");
+
+    for line in [
+        "// just a comment (really)",
+        "// a(); // doesn't work",
+        "// a(); // doesn't work (E03)",
+        "// // doesn't work (E0308)",
+        "a(); // doesn't work (E0308)",
+    ] {
+        assert!(parse_failing_line(line).is_none(), "{line}");
+    }
+}
+
+#[test]
+fn each_failing_line_gets_a_case() {
+    let snippet = snippet(&[
+        "fn main() {",
+        "    // a(1); // doesn't work (E0308)",
+        "    // just a comment (really)",
+        "    // b(); // doesn't work (E0425)",
+        "}",
+    ]);
+    let cases = compile_fail_cases(&snippet);
+    assert_eq!(cases.len(), 2);
+
+    assert_eq!(cases[0].snippet.name, "code_10_fails_12");
+    assert_eq!(cases[0].line.line, 12);
+    assert_eq!(cases[0].error_code, "E0308");
+    let (source, origins) = render(&cases[0].snippet);
+    assert!(source.contains("\n    a(1);\n    // just a comment (really)\n    // b();"));
+    // The uncommented line still maps to the comment.
+    let line = source.lines().position(|line| line == "    a(1);").unwrap();
+    assert_eq!(origins[line].line, 12);
+
+    assert_eq!(cases[1].snippet.name, "code_10_fails_14");
+    assert_eq!(cases[1].error_code, "E0425");
+    let (source, _) = render(&cases[1].snippet);
+    assert!(source.contains("\n    // a(1);"));
+    assert!(source.contains("\n    b();\n}"));
+}
+
+#[test]
+fn paths_point_at_the_book() {
+    assert_eq!(
+        remap(
+            "error at examples/code_10.rs:4:9 and examples/code_10.rs:5",
+            &maps()
+        ),
+        "error at src/code.md:12:11 and src/code.md:13"
+    );
+    // Not one of ours, or not a line we know about.
+    let unknown = "examples/other.rs:4:9 examples/code_10.rs:99:1";
+    assert_eq!(remap(unknown, &maps()), unknown);
+}
+
+#[test]
+fn gutter_points_at_the_book() {
+    let rendered = "\
+error: unused variable
+ --> examples/code_10.rs:4:5
+  |
+4 |     let x = 1;
+  |         ^ help: prefix it with an underscore: `_x`
+  |
+  = note: `#[warn(unused_variables)]` on by default
+";
+    assert_eq!(
+        remap(rendered, &maps()),
+        "\
+error: unused variable
+  --> src/code.md:12:7
+   |
+12 |     let x = 1;
+   |         ^ help: prefix it with an underscore: `_x`
+   |
+   = note: `#[warn(unused_variables)]` on by default
+"
+    );
+}
+
+#[test]
+fn gutter_of_other_files_is_kept() {
+    let rendered = "\
+error[E0308]: mismatched types
+ --> examples/code_10.rs:3:1
+  |
+3 | f(1);
+  | ^^^^
+  |
+note: function defined here
+ --> pets/src/lib.rs:5:4
+  |
+5 | fn f() {}
+  |    ^
+";
+    let remapped = remap(rendered, &maps());
+    assert!(remapped.contains("\n11 | f(1);\n"), "{remapped}");
+    assert!(remapped.contains("\n 5 | fn f() {}\n"), "{remapped}");
+}
+
+#[test]
+fn coloured_gutter_points_at_the_book() {
+    let blue = "\x1b[1m\x1b[94m";
+    let reset = "\x1b[0m";
+    let rendered = format!(
+        " {blue}--> {reset}examples/code_10.rs:4:5\n  {blue}|{reset}\n\
+         {blue}4{reset} {blue}|{reset}     let x = 1;\n"
+    );
+    assert_eq!(
+        remap(&rendered, &maps()),
+        format!(
+            "  {blue}--> {reset}src/code.md:12:7\n   {blue}|{reset}\n\
+             {blue}12{reset} {blue}|{reset}     let x = 1;\n"
+        )
+    );
+}
@@ -0,0 +1,169 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An [mdbook preprocessor](https://rust-lang.github.io/mdBook/for_developers/preprocessors.html)
+//! which lets a code block start with shared code that readers don't see.
+//!
+//! ````text
+//! ```rust
+//! {{#include_hidden ../pets/src/animal.rs}}
+//! fn make_shopping_list() { /* uses PETS */ }
+//! ```
+//! ````
+//!
+//! inlines `animal.rs` with `# ` in front of every line, so `mdbook test`
+//! compiles it but the rendered book hides it. Unlike hand-written hidden
+//! lines, the included file is ordinary Rust which can be built, linted and
+//! formatted. Paths are relative to the chapter, as for `{{#include}}`.
+//!
+//! Boilerplate which many code blocks share can instead be given a name in
+//! `book.toml`, as a list of files relative to the book's `src` directory:
+//!
+//! ```toml
+//! [preprocessor.include-hidden.preludes]
+//! pets = ["../pets/src/animal.rs", "preludes/shopping.rs"]
+//! ```
+//!
+//! A code block whose info string says `rust,prelude=pets` then starts with
+//! those files as hidden lines. The `prelude=` attribute itself is removed.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+const DIRECTIVE: &str = "{{#include_hidden";
+
+const PRELUDE_ATTRIBUTE: &str = "prelude=";
+
+/// Named lists of files, from `[preprocessor.include-hidden.preludes]`.
+pub type Preludes = HashMap<String, Vec<PathBuf>>;
+
+/// Appends the lines of the file at `path` to `output`, each prefixed with
+/// `indent` and then `# `.
+fn push_hidden(output: &mut String, indent: &str, path: &Path) -> Result<(), String> {
+    let included = fs::read_to_string(path)
+        .map_err(|e| format!("unable to include {}: {e}", path.display()))?;
+    for line in included.lines() {
+        output.push_str(indent);
+        if line.is_empty() {
+            output.push('#');
+        } else {
+            output.push_str("# ");
+            output.push_str(line);
+        }
+        output.push('\n');
+    }
+    Ok(())
+}
+
+/// Replaces every `{{#include_hidden path}}` line in `content` with the
+/// lines of the file at `path`, relative to `dir`, and starts every code
+/// block which asks for a prelude with that prelude's files. Whatever
+/// indentation the directive or code block has is kept, so this works in
+/// code blocks nested inside lists.
+pub fn expand(content: &str, dir: &Path, preludes: &Preludes) -> Result<String, String> {
+    let mut output = String::with_capacity(content.len());
+    // The fence of the code block we're in, if any.
+    let mut fence: Option<&str> = None;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        if let Some(open) = fence {
+            if trimmed.trim_end() == open {
+                fence = None;
+            } else if let Some(path) = parse_directive(trimmed) {
+                push_hidden(&mut output, indent, &dir.join(path))?;
+                continue;
+            }
+        } else if let Some((open, info)) = parse_fence(trimmed) {
+            fence = Some(open);
+            let (info, names) = take_preludes(info);
+            if !names.is_empty() {
+                output.push_str(indent);
+                output.push_str(open);
+                output.push_str(&info);
+                output.push('\n');
+                for name in names {
+                    let files = preludes
+                        .get(name)
+                        .ok_or_else(|| format!("no prelude called {name:?} in book.toml"))?;
+                    for file in files {
+                        push_hidden(&mut output, indent, file)?;
+                    }
+                }
+                continue;
+            }
+        }
+        output.push_str(line);
+    }
+    Ok(output)
+}
+
+/// If `line` (without indentation) is an `include_hidden` directive, returns
+/// its path.
+pub fn parse_directive(line: &str) -> Option<&str> {
+    let path = line
+        .trim_end()
+        .strip_prefix(DIRECTIVE)?
+        .strip_suffix("}}")?
+        .trim();
+    (!path.is_empty()).then_some(path)
+}
+
+/// If `line` (without indentation) opens a code block, returns the fence and
+/// the info string.
+pub fn parse_fence(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_end();
+    let fence_char = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = line.len() - line.trim_start_matches(fence_char).len();
+    (fence_len >= 3).then(|| line.split_at(fence_len))
+}
+
+/// Splits `prelude=name` attributes out of a code block's info string.
+pub fn take_preludes(info: &str) -> (String, Vec<&str>) {
+    let mut names = Vec::new();
+    let mut rest = Vec::new();
+    for attribute in info.split(',') {
+        match attribute.trim().strip_prefix(PRELUDE_ATTRIBUTE) {
+            Some(name) => names.push(name),
+            None => rest.push(attribute),
+        }
+    }
+    (rest.join(","), names)
+}
+
+/// Reads `[preprocessor.include-hidden.preludes]` from the book's config.
+pub fn read_preludes(config: &Value, src_dir: &Path) -> Result<Preludes, String> {
+    let Some(table) = config["preprocessor"]["include-hidden"]["preludes"].as_object() else {
+        return Ok(Preludes::new());
+    };
+    table
+        .iter()
+        .map(|(name, files)| {
+            let files = files
+                .as_array()
+                .ok_or_else(|| format!("prelude {name:?} should be a list of files"))?
+                .iter()
+                .map(|file| {
+                    file.as_str()
+                        .map(|file| src_dir.join(file))
+                        .ok_or_else(|| format!("prelude {name:?} should be a list of files"))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok((name.clone(), files))
+        })
+        .collect()
+}
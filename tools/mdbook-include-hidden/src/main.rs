@@ -0,0 +1,76 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `mdbook-include-hidden` preprocessor itself. See the library for what
+//! it does.
+//!
+//! We speak the preprocessor protocol directly in JSON, rather than through
+//! the `mdbook` crate, so that this builds quickly and works with whichever
+//! `mdbook` is installed.
+
+use std::io;
+use std::path::Path;
+use std::process::exit;
+
+use mdbook_include_hidden::{expand, read_preludes, Preludes};
+use serde_json::Value;
+
+/// Expands directives in each chapter in `items`, a list of `BookItem`s.
+fn expand_items(items: &mut [Value], src_dir: &Path, preludes: &Preludes) -> Result<(), String> {
+    for item in items {
+        let Some(chapter) = item.get_mut("Chapter") else {
+            continue; // a separator or part title
+        };
+        // Draft chapters have no path, and no content either.
+        if let Some(path) = chapter["path"].as_str() {
+            let dir = src_dir.join(path);
+            let dir = dir.parent().unwrap_or(src_dir);
+            let content = chapter["content"].as_str().unwrap_or_default();
+            let expanded = expand(content, dir, preludes).map_err(|e| format!("{path}: {e}"))?;
+            chapter["content"] = Value::String(expanded);
+        }
+        if let Some(sub_items) = chapter["sub_items"].as_array_mut() {
+            expand_items(sub_items, src_dir, preludes)?;
+        }
+    }
+    Ok(())
+}
+
+fn preprocess() -> Result<(), String> {
+    let input: Value =
+        serde_json::from_reader(io::stdin()).map_err(|e| format!("bad input from mdbook: {e}"))?;
+    let [context, mut book]: [Value; 2] = serde_json::from_value(input)
+        .map_err(|_| "expected [context, book] from mdbook".to_string())?;
+    let root = Path::new(context["root"].as_str().unwrap_or("."));
+    let src_dir = root.join(context["config"]["book"]["src"].as_str().unwrap_or("src"));
+    let preludes = read_preludes(&context["config"], &src_dir)?;
+    // mdbook 0.4 calls the list of chapters `sections`.
+    let items = book["sections"]
+        .as_array_mut()
+        .ok_or("book has no sections")?;
+    expand_items(items, &src_dir, &preludes)?;
+    serde_json::to_writer(io::stdout(), &book).map_err(|e| e.to_string())
+}
+
+fn main() {
+    // `mdbook-include-hidden supports <renderer>`: we only touch markdown, so
+    // we work with every renderer, including `mdbook test`.
+    if std::env::args().nth(1).as_deref() == Some("supports") {
+        exit(0);
+    }
+    if let Err(message) = preprocess() {
+        eprintln!("mdbook-include-hidden: {message}");
+        exit(1);
+    }
+}
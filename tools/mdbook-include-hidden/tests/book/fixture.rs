@@ -0,0 +1,3 @@
+struct Animal;
+
+static DUCK: Animal = Animal;
@@ -0,0 +1,123 @@
+// Copyright 2020 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use serde_json::{json, Value};
+
+fn run(chapter_content: &str) -> Value {
+    let output = run_raw(chapter_content);
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn run_raw(chapter_content: &str) -> Output {
+    let root = env!("CARGO_MANIFEST_DIR");
+    let input = json!([
+        {
+            "root": root,
+            "config": {
+                "book": { "src": "tests/book" },
+                "preprocessor": { "include-hidden": { "preludes": {
+                    "animals": ["fixture.rs"],
+                    "twice": ["fixture.rs", "fixture.rs"],
+                } } },
+            },
+            "renderer": "html",
+        },
+        { "sections": [
+            { "Chapter": {
+                "name": "Pets",
+                "content": chapter_content,
+                "path": "pets.md",
+                "sub_items": [],
+            } },
+            "Separator",
+            { "Chapter": { "name": "Draft", "content": "", "path": null, "sub_items": [] } },
+        ] },
+    ]);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mdbook-include-hidden"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.to_string().as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn content(book: &Value) -> &str {
+    book["sections"][0]["Chapter"]["content"].as_str().unwrap()
+}
+
+#[test]
+fn includes_file_as_hidden_lines() {
+    let book = run("```rust\n{{#include_hidden fixture.rs}}\nfn main() {}\n```\n");
+    assert_eq!(
+        content(&book),
+        "```rust\n# struct Animal;\n#\n# static DUCK: Animal = Animal;\nfn main() {}\n```\n"
+    );
+}
+
+#[test]
+fn keeps_indentation() {
+    let book = run("* List\n  ```rust\n  {{#include_hidden fixture.rs}}\n  ```\n");
+    assert_eq!(
+        content(&book),
+        "* List\n  ```rust\n  # struct Animal;\n  #\n  # static DUCK: Animal = Animal;\n  ```\n"
+    );
+}
+
+#[test]
+fn adds_named_preludes() {
+    let book = run("  ```rust,prelude=animals,should_panic\n  panic!();\n  ```\n");
+    assert_eq!(
+        content(&book),
+        "  ```rust,should_panic\n  # struct Animal;\n  #\n  # static DUCK: Animal = Animal;\n  panic!();\n  ```\n"
+    );
+    let book = run("```rust,prelude=twice\n```\n");
+    assert_eq!(content(&book).matches("struct Animal").count(), 2);
+}
+
+#[test]
+fn leaves_everything_else_alone() {
+    let text = "Some {{#include other.rs}} text\n{{#include_hidden}}\n";
+    let book = run(text);
+    assert_eq!(content(&book), text);
+    assert_eq!(book["sections"][1], "Separator");
+}
+
+#[test]
+fn unknown_prelude_is_an_error() {
+    let output = run_raw("```rust,prelude=unicorns\n```\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("\"unicorns\""));
+}
+
+#[test]
+fn supports_every_renderer() {
+    for renderer in ["html", "test", "linkcheck"] {
+        let status = Command::new(env!("CARGO_BIN_EXE_mdbook-include-hidden"))
+            .args(["supports", renderer])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}